@@ -3,7 +3,7 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, LitStr};
+use syn::{parse_macro_input, spanned::Spanned, LitStr};
 
 /// # 示例:
 /// ```rs
@@ -71,12 +71,321 @@ fn contains_default_flags(value: &str) -> bool {
     let arr: Vec<String> = vec![
         "-h".to_string(),
         "--help".to_string(),
-        // "-e".to_string(),
-        // "--example".to_string(),
+        "-e".to_string(),
+        "--example".to_string(),
         "-v".to_string(),
         "--version".to_string(),
-        // "--list-all-commands".to_string(),
+        "--list-all-commands".to_string(),
+        "--dry-run".to_string(),
+        "--yes".to_string(),
     ];
 
     arr.contains(&string_value)
 }
+
+/// 为结构体实现 `chenbao_cmd::FromDialog`, 按照字段的顺序依次提问, 返回填写好的结构体.
+/// 交互式问答和从 toml 读取答案 (`app cmd stdin`) 都可以使用.
+///
+/// 字段的类型决定了提问的方式:
+/// * `String` -> `string()`, 设置了 `choices` 时是 `select()`, 设置了 `editor` 时是 `editor()`
+/// * `Vec<String>` -> `string_multiple()`, 设置了 `choices` 时是 `select_multiple()`
+/// * `i64` -> `number()`, `Vec<i64>` -> `number_multiple()`
+/// * `bool` -> `yes_or_no()`
+/// * `PathBuf` -> `path()`, `Vec<PathBuf>` -> `path_multiple()`
+/// * 其他实现了 `FromDialog` 的类型 -> `section()`, 答案保存在 toml 中名为 key 的表中.
+/// * `Vec<T>`, `T` 实现了 `FromDialog` -> `repeat()`, prompt 是每次添加之前询问是否添加的问题.
+/// * 其他类型 (例如 `u16`, `f64`, `Option<T>`) 不支持, 编译时报错.
+///
+/// `section()` `repeat()` 的字段只能使用 `prompt` 和 `key`.
+///
+/// 字段的属性 `#[dialog(...)]`:
+/// * `prompt = "..."`: 问题, 默认是字段名.
+/// * `key = "..."`: 答案在 toml 中的 key, 默认是字段名. 修改问题的文字或者字段的顺序后, 之前保存的 toml 依然可以使用.
+/// * `default = ...`: 默认答案, 例如 `default = "dev"`, `default = 8080`, `default = true`.
+/// * `initial_text = "..."`: 输入框中的初始文字.
+/// * `help = "..."`: 显示在问题下面的帮助信息.
+/// * `allow_empty`: 可以不填.
+/// * `choices("a", "b")`: 可选项.
+/// * `editor`: 使用编辑器输入.
+/// * `validator = path::to::func`: 校验答案, `fn(&str) -> Result<(), String>`, 参数是文字形式的答案, 多个值时对每个值分别调用.
+///   和 `Validator::Custom` 一样, 交互式问答时校验失败会重新提问.
+///
+/// # 示例:
+/// ```rs
+/// #[derive(FromDialog)]
+/// struct Deploy {
+///     #[dialog(prompt = "部署到哪个环境?", choices("dev", "prod"))]
+///     env: String,
+///     #[dialog(prompt = "实例数量", validator = check_count)]
+///     count: i64,
+/// }
+///
+/// cmd!("deploy").action(Arg::Dialog(&|r| {
+///     let deploy = Deploy::from_dialog(r).unwrap();
+/// }));
+/// ```
+#[proc_macro_derive(FromDialog, attributes(dialog))]
+pub fn derive_from_dialog(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+
+    match from_dialog_impl(&input) {
+        Ok(x) => x.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// 一个字段的 `#[dialog(...)]` 属性.
+#[derive(Default)]
+struct DialogField {
+    prompt: Option<LitStr>,
+    key: Option<LitStr>,
+    default: Option<syn::Lit>,
+    initial_text: Option<LitStr>,
+    help: Option<LitStr>,
+    allow_empty: bool,
+    choices: Option<Vec<LitStr>>,
+    editor: bool,
+    validator: Option<syn::Path>,
+}
+
+impl DialogField {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut re = DialogField::default();
+
+        for attr in field.attrs.iter().filter(|x| x.path().is_ident("dialog")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prompt") {
+                    re.prompt = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("key") {
+                    re.key = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default") {
+                    re.default = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("initial_text") {
+                    re.initial_text = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("help") {
+                    re.help = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("allow_empty") {
+                    re.allow_empty = true;
+                } else if meta.path.is_ident("choices") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let items = content.parse_terminated(
+                        |x: syn::parse::ParseStream| x.parse::<LitStr>(),
+                        syn::Token![,],
+                    )?;
+                    re.choices = Some(items.into_iter().collect());
+                } else if meta.path.is_ident("editor") {
+                    re.editor = true;
+                } else if meta.path.is_ident("validator") {
+                    re.validator = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta
+                        .error("不支持的属性, 可以使用: prompt, key, default, initial_text, help, allow_empty, choices, editor, validator"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(re)
+    }
+}
+
+fn from_dialog_impl(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(x),
+            ..
+        }) => &x.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "FromDialog 只能用于有命名字段的结构体.",
+            ))
+        }
+    };
+
+    let mut questions = vec![];
+    let mut names = vec![];
+
+    for field in fields {
+        let name = field.ident.as_ref().unwrap();
+        let attr = DialogField::parse(field)?;
+
+        let prompt = attr
+            .prompt
+            .clone()
+            .unwrap_or_else(|| LitStr::new(&name.to_string(), name.span()));
+        let key = attr
+            .key
+            .clone()
+            .unwrap_or_else(|| LitStr::new(&name.to_string(), name.span()));
+
+        let mut is_question = true;
+        let ask = match (type_name(&field.ty).as_str(), &attr.choices) {
+            ("String", Some(choices)) => quote! { r.select(#prompt, &vec![#(#choices),*]) },
+            ("String", None) if attr.editor => quote! { r.editor(#prompt) },
+            ("String", None) => quote! { r.string(#prompt) },
+            ("Vec<String>", Some(choices)) => {
+                quote! { r.select_multiple(#prompt, &vec![#(#choices),*]) }
+            }
+            ("Vec<String>", None) => quote! { r.string_multiple(#prompt) },
+            ("i64", None) => quote! { r.number(#prompt) },
+            ("Vec<i64>", None) => quote! { r.number_multiple(#prompt) },
+            ("bool", None) => quote! { r.yes_or_no(#prompt) },
+            ("PathBuf", None) => quote! { r.path(#prompt) },
+            ("Vec<PathBuf>", None) => quote! { r.path_multiple(#prompt) },
+            (_, Some(_)) => {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "choices 只能用于 String 或者 Vec<String> 类型的字段.",
+                ))
+            }
+            _ => {
+                is_question = false;
+                match vec_inner(&field.ty) {
+                    Some(ty) if is_dialog_struct(ty) => quote! {
+                        r.repeat(#key, #prompt, |r| <#ty as chenbao_cmd::FromDialog>::from_dialog(r))
+                    },
+                    None if is_dialog_struct(&field.ty) => {
+                        let ty = &field.ty;
+                        quote! {
+                            r.section(#key, |r| <#ty as chenbao_cmd::FromDialog>::from_dialog(r))
+                        }
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &field.ty,
+                            "不支持的类型, 可以使用: String, Vec<String>, i64, Vec<i64>, bool, PathBuf, Vec<PathBuf>, \
+                             实现了 FromDialog 的结构体 (section) 或者 Vec<结构体> (repeat).",
+                        ))
+                    }
+                }
+            }
+        };
+
+        if !is_question {
+            // 这些属性是一个问题的选项, section() 和 repeat() 不会使用.
+            let options = [
+                ("default", attr.default.as_ref().map(|x| x.span())),
+                ("initial_text", attr.initial_text.as_ref().map(|x| x.span())),
+                ("help", attr.help.as_ref().map(|x| x.span())),
+                ("allow_empty", attr.allow_empty.then(|| name.span())),
+                ("validator", attr.validator.as_ref().map(|x| x.span())),
+            ];
+            if let Some((option, span)) = options.into_iter().find_map(|(x, span)| Some((x, span?)))
+            {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "{} 只能用于问题的字段, 不能用于 section() 或者 repeat() 的字段.",
+                        option
+                    ),
+                ));
+            }
+        }
+        let validate = attr
+            .validator
+            .as_ref()
+            .map(|f| quote! { r.validate(chenbao_cmd::Validator::Custom(&#f)); });
+
+        let default = attr
+            .default
+            .as_ref()
+            .map(|x| quote! { r.default_answer(#x); });
+        let initial_text = attr
+            .initial_text
+            .as_ref()
+            .map(|x| quote! { r.initial_text(#x); });
+        let help = attr.help.as_ref().map(|x| quote! { r.help(#x); });
+        let allow_empty = attr.allow_empty.then(|| quote! { r.allow_empty(true); });
+
+        questions.push(quote! {
+            r.key(#key);
+            #default
+            #initial_text
+            #help
+            #allow_empty
+            #validate
+            let #name = #ask?;
+        });
+        names.push(name);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics chenbao_cmd::FromDialog for #ident #ty_generics #where_clause {
+            fn from_dialog(
+                r: &mut chenbao_cmd::arg_type::Dialog,
+            ) -> std::result::Result<Self, std::string::String> {
+                #(#questions)*
+
+                Ok(Self { #(#names),* })
+            }
+        }
+    })
+}
+
+/// 是否可能是实现了 `FromDialog` 的结构体, 不是问题的类型也不是其他常见的类型.
+fn is_dialog_struct(ty: &syn::Type) -> bool {
+    const NOT_STRUCT: &[&str] = &[
+        "String", "PathBuf", "i64", "bool", "Vec", "Option", "Box", "u8", "u16", "u32", "u64",
+        "u128", "usize", "i8", "i16", "i32", "i128", "isize", "f32", "f64", "char", "str",
+        "HashMap", "BTreeMap", "HashSet", "BTreeSet",
+    ];
+
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|x| !NOT_STRUCT.contains(&x.ident.to_string().as_str()))
+}
+
+/// `Vec<T>` 中的 `T`.
+fn vec_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last = type_path.path.segments.last()?;
+    if last.ident != "Vec" {
+        return None;
+    }
+
+    match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(t) => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 字段类型的名称, 去掉路径, 例如 `std::path::PathBuf` -> `PathBuf`, `Vec<std::string::String>` -> `Vec<String>`.
+fn type_name(ty: &syn::Type) -> String {
+    let syn::Type::Path(type_path) = ty else {
+        return String::new();
+    };
+    let Some(last) = type_path.path.segments.last() else {
+        return String::new();
+    };
+
+    match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) => {
+            let inner: Vec<String> = args
+                .args
+                .iter()
+                .map(|x| match x {
+                    syn::GenericArgument::Type(t) => type_name(t),
+                    _ => String::new(),
+                })
+                .collect();
+            format!("{}<{}>", last.ident, inner.join(", "))
+        }
+        _ => last.ident.to_string(),
+    }
+}
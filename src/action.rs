@@ -135,7 +135,11 @@ impl Arg {
                     z = r#"true false true false"#.styled_arg(),
                 )
             }
-            Arg::Dialog(_) => "".to_string(),
+            Arg::Dialog(_) => format!(
+                r#"没有参数时启动问答式交互 (stdin 不是终端时按问题的顺序每行读取一个答案), 也可以使用之前保存的答案: {stdin} 或者 {path}"#,
+                stdin = r#"stdin"#.styled_arg(),
+                path = r#""./cmd.answers.toml""#.styled_arg(),
+            ),
         };
 
         format!("    {}", arg_tips)
@@ -197,22 +201,24 @@ impl Arg {
     //     re.to_string()
     // }
 
-    // pub(crate) fn arg_type_display(&self) -> String {
-    //     let re = match self {
-    //         ArgAction::Empty(_) => "",
-    //         ArgAction::String(_) => r#"<String>"#,
-    //         ArgAction::StringMutiple(_) => r#"<String>..."#,
-    //         ArgAction::Number(_) => r#"<Number>"#,
-    //         ArgAction::NumberMutiple(_) => r#"<Number>"#,
-    //         ArgAction::Path(_) => r#"<Path>"#,
-    //         ArgAction::PathMutiple(_) => r#"<Path>..."#,
-    //         ArgAction::Bool(_) => r#"<bool>"#,
-    //         ArgAction::BoolMutiple(_) => r#"<bool>..."#,
-    //         ArgAction::Dialog(_) => "",
-    //     };
-    //
-    //     re.to_string()
-    // }
+    /// 参数类型的名称, 没有颜色.
+    /// `app --list-all-commands toml` 时会用到.
+    pub(crate) fn arg_type_display(&self) -> String {
+        let re = match self {
+            Arg::Empty(_) => "",
+            Arg::String(_) => "String",
+            Arg::StringMutiple(_) => "String...",
+            Arg::Number(_) => "Number",
+            Arg::NumberMutiple(_) => "Number...",
+            Arg::Path(_) => "Path",
+            Arg::PathMutiple(_) => "Path...",
+            Arg::Bool(_) => "Bool",
+            Arg::BoolMutiple(_) => "Bool...",
+            Arg::Dialog(_) => "Dialog",
+        };
+
+        re.to_string()
+    }
 }
 
 /// 子命令实际接收到的参数
@@ -452,11 +458,22 @@ impl SubcommandArgsValue {
                         }
                     }
                 }
+
+                // 答案文件的路径.
+                return match std::fs::read_to_string(repl_first_arg) {
+                    Ok(toml_string) => Ok(Some(toml_string)),
+                    Err(err) => Err(format!(
+                        "{}: {} {}",
+                        "无法读取答案文件".styled_error_marker(),
+                        repl_first_arg.styled_arg(),
+                        err
+                    )),
+                };
             }
         }
 
         Err(format!(
-            "{}: 需要 0 个参数 或者 自动生成的'快捷参数', 实际接收到了 {} 个参数: {:?}",
+            "{}: 需要 0 个参数, stdin 或者 答案文件的路径, 实际接收到了 {} 个参数: {:?}",
             "参数数量错误".styled_error_marker(),
             subcmd_args.len().styled_sub_command(),
             subcmd_args,
@@ -1,30 +1,34 @@
 use crate::helper::*;
-use crate::subcommand::ErrorTable;
+use crate::hook::{self, Hook, HookFn, Invocation, Middleware, MiddlewareFn};
+use crate::plugin::Plugin;
+use crate::script::{ScriptLineResult, ScriptResult};
+use crate::subcommand::{CommandInfo, ErrorTable, Sadadsf};
 use crate::*;
 use crate::{examples_types::Examples, subcommand::ExampleTestResult};
 use core::fmt;
 use owo_colors::OwoColorize;
-use prettytable::{cell, row, table, Row};
+use prettytable::{cell, row, table, Row, Table};
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub(crate) enum AppDefaultAction {
     /// 打印 app 的帮助文档
+    #[default]
     PrintHelpMessage,
 
     /// 如果想读取命令行参数, 请使用:   `let env_arg: Vec<String> = env::args().collect();`
     CustomAction(&'static dyn Fn()),
-}
 
-impl Default for AppDefaultAction {
-    fn default() -> Self {
-        Self::PrintHelpMessage
-    }
+    /// 让用户从列表中选择一个子命令, 然后通过问答式交互输入参数.
+    /// stdin 不是 terminal 时打印 app 的帮助文档.
+    PickCommand,
 }
+
 impl fmt::Debug for AppDefaultAction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::PrintHelpMessage => write!(f, "PrintHelpMessage"),
             Self::CustomAction(_) => f.debug_tuple("CustomAction(_)").finish(),
+            Self::PickCommand => write!(f, "PickCommand"),
         }
     }
 }
@@ -37,13 +41,27 @@ pub enum DidHandled {
 
     /// 没匹配到相关命令或者其他错误.
     Failed(String),
+
+    /// 和 `Failed` 一样表示执行失败, 并且 `App::run()` 需要以这个退出码退出.
+    /// 例如外部子命令的退出码不是 0.
+    FailedWithCode(i32, String),
 }
 
 impl DidHandled {
+    /// `App::run()` 的退出码, `Handled` 是 0, `Failed` 是 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DidHandled::Handled => 0,
+            DidHandled::Failed(_) => 1,
+            DidHandled::FailedWithCode(code, _) => *code,
+        }
+    }
+
     pub fn map_err<O: FnOnce(String) -> String>(self, op: O) -> DidHandled {
         match self {
             DidHandled::Handled => self,
             DidHandled::Failed(e) => DidHandled::Failed(op(e)),
+            DidHandled::FailedWithCode(code, e) => DidHandled::FailedWithCode(code, op(e)),
         }
     }
 }
@@ -69,6 +87,9 @@ pub struct App {
     /// 此 app 的所有子命令.
     _commands: Vec<SubCommand>,
 
+    /// 帮助文档中子命令分组的显示顺序.
+    _group_order: Vec<String>,
+
     /// 使用此程序的一些示范和例子.
     /// 自动生成帮助文档时会用的这里面的例子.
     _app_examples: Examples,
@@ -86,6 +107,32 @@ pub struct App {
     /// 标记是否需要执行 SubCommand 的 action.
     /// 默认是 NeedTo::Run
     _need_to: NeedTo,
+
+    /// 执行任意子命令的 action 之前的 hook.
+    _before_hooks: Vec<Hook>,
+
+    /// 执行任意子命令的 action 之后的 hook.
+    _after_hooks: Vec<Hook>,
+
+    /// 包裹子命令执行过程的 middleware, 第一个在最外层.
+    _middlewares: Vec<Middleware>,
+
+    /// 是否启用 multi-call 模式.
+    /// 启用后, 如果程序是通过子命令的名称 (例如软链接) 执行的, 则直接执行这个子命令.
+    _multi_call: bool,
+
+    /// 是否启用 PATH 中的外部子命令 `<app_name>-<command>`.
+    _plugins_enabled: bool,
+
+    /// 在这些目录中查找外部子命令, 为空时使用 PATH 中的目录.
+    _plugin_dirs: Vec<std::path::PathBuf>,
+
+    /// 是否启用 `app shell` 交互式 shell.
+    _shell_enabled: bool,
+
+    /// `app shell` 的 history 文件.
+    /// 默认是 `$HOME/.{app_name}_history`
+    _shell_history_file: Option<std::path::PathBuf>,
 }
 
 impl App {
@@ -123,16 +170,22 @@ impl App {
         re
     }
 
-    // /// 使用此程序的一些示例,
-    // /// 当用户使用 `app -e` 时会打印在这里添加的示例.
-    // /// 此 method 可以多次调用来给此程序添加多个示例.
-    // pub fn add_app_example(self, command: &str, description: &str) -> Self {
-    //     let mut re = self;
+    /// 使用此程序的一些示例,
+    /// 当用户使用 `app -e` 时会打印在这里添加的示例.
+    /// 此 method 可以多次调用来给此程序添加多个示例.
+    /// 如果没有添加过示例, `app -e` 会打印所有子命令的示例.
+    /// ```
+    ///     let app = chenbao_cmd::App::new()
+    ///         .app_name("app")
+    ///         .add_app_example("app -h", "查看帮助文档");
+    /// ```
+    pub fn add_app_example(self, command: &str, description: &str) -> Self {
+        let mut re = self;
 
-    //     re._app_examples.add_single_example(command, description);
+        re._app_examples.add_single_example(command, description);
 
-    //     re
-    // }
+        re
+    }
 
     /// 此程序的版本信息.
     /// 当用户使用 `app --version` 时会打印在这里添加的版本信息.
@@ -167,6 +220,15 @@ impl App {
         re
     }
 
+    /// 只有 程序名, 没有任何子命令也没有任何参数时,
+    /// 让用户从列表中选择一个子命令, 然后根据子命令的 `Arg` 类型询问参数.
+    /// 如果 stdin 不是 terminal, 则打印此程序的帮助信息.
+    pub fn app_default_pick_command(self) -> Self {
+        let mut re = self;
+        re._app_default_action = AppDefaultAction::PickCommand;
+        re
+    }
+
     /// ### 为此 App 添加指令
     /// 示例:
     /// ```
@@ -182,6 +244,138 @@ impl App {
         re
     }
 
+    /// 添加执行任意子命令的 action 之前的 hook, 可以多次调用, 例如初始化日志, 读取配置.
+    /// hook 的参数是子命令的名称和子命令接收到的参数, 返回 `Err` 时不会执行子命令.
+    /// 会在子命令自己的 `SubCommand::before()` 之前调用.
+    /// `-h` `-e` `-v` 和只解析不执行时不会调用 hook.
+    /// ```
+    ///     let app = chenbao_cmd::App::new().before(&|name, _args| {
+    ///         println!("running {}", name);
+    ///         Ok(())
+    ///     });
+    /// ```
+    pub fn before(self, hook: &'static HookFn) -> Self {
+        let mut re = self;
+        re._before_hooks.push(Hook(hook));
+        re
+    }
+
+    /// 添加执行任意子命令的 action 之后的 hook, 可以多次调用, 例如上传统计数据.
+    /// 会在子命令自己的 `SubCommand::after()` 之后调用.
+    /// 即使子命令执行失败也会调用, 返回 `Err` 时执行结果是失败.
+    /// `-h` `-e` `-v` 和只解析不执行时不会调用 hook.
+    pub fn after(self, hook: &'static HookFn) -> Self {
+        let mut re = self;
+        re._after_hooks.push(Hook(hook));
+        re
+    }
+
+    /// 添加包裹子命令执行过程的 middleware, 可以多次调用, 先添加的在外层.
+    /// middleware 可以在执行子命令的前后做一些事情, 例如计时, 重试, 捕获 panic, 审计日志, 权限检查.
+    /// 调用 `next()` 执行下一层 middleware, 最里面一层是子命令本身.
+    /// `-h` `-e` `-v` 和只解析不执行时不会调用 middleware.
+    /// ```
+    ///     use chenbao_cmd::*;
+    ///
+    ///     fn timing(inv: &Invocation, next: &dyn Fn() -> DidHandled) -> DidHandled {
+    ///         let start = std::time::Instant::now();
+    ///         let re = next();
+    ///         eprintln!("{} 用时 {:?}", inv.cmd_name, start.elapsed());
+    ///         re
+    ///     }
+    ///
+    ///     fn catch_panic(inv: &Invocation, next: &dyn Fn() -> DidHandled) -> DidHandled {
+    ///         std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| next()))
+    ///             .unwrap_or_else(|_| DidHandled::Failed(format!("{} panicked", inv.cmd_name)))
+    ///     }
+    ///
+    ///     let app = App::new().middleware(&timing).middleware(&catch_panic);
+    /// ```
+    pub fn middleware(self, middleware: &'static MiddlewareFn) -> Self {
+        let mut re = self;
+        re._middlewares.push(Middleware(middleware));
+        re
+    }
+
+    /// 启用 multi-call 模式, 像 busybox 一样用一个程序提供多个命令.
+    /// 如果执行程序时使用的名称 (`argv[0]`, 例如软链接的名称) 和某个子命令的名称或者 short_name 相同,
+    /// 则直接执行这个子命令, 所有的命令行参数都会交给这个子命令.
+    /// ```sh
+    /// ln -s ./app ./build
+    /// ./build true      # 等同于 ./app build true
+    /// ```
+    pub fn multi_call(self) -> Self {
+        let mut re = self;
+        re._multi_call = true;
+        re
+    }
+
+    /// 启用外部子命令, 类似 `git foo` 执行 `git-foo`.
+    /// 没有匹配到子命令时, 在 PATH 中查找 `<app_name>-<command>` 并执行, 剩下的参数都会交给它.
+    /// 外部子命令执行失败时, 此程序会以相同的 exit code 退出.
+    /// 找到的外部子命令会显示在 `app -h` 和 `app --list-all-commands` 的 Plugins 部分.
+    pub fn enable_plugins(self) -> Self {
+        let mut re = self;
+        re._plugins_enabled = true;
+        re
+    }
+
+    /// 启用 `app shell` 交互式 shell.
+    /// 在 shell 中输入的每一行都会像 `app <command> [arguments]` 一样被执行,
+    /// 支持 history, Tab 键补全子命令名称和路径, 以及 `help` `exit` 命令.
+    /// ```
+    ///     let app = chenbao_cmd::App::new().enable_shell();
+    /// ```
+    pub fn enable_shell(self) -> Self {
+        let mut re = self;
+        re._shell_enabled = true;
+        re
+    }
+
+    /// 设置 `app shell` 的 history 文件.
+    /// 默认是 `$HOME/.{app_name}_history`
+    pub fn shell_history_file(self, path: &str) -> Self {
+        let mut re = self;
+        re._shell_history_file = Some(std::path::PathBuf::from(path));
+        re
+    }
+
+    /// 设置帮助文档中子命令分组的显示顺序.
+    /// 没有在这里出现的分组会按照第一次出现的顺序显示在后面.
+    /// ```
+    ///     use chenbao_cmd::*;
+    ///     let app = App::new()
+    ///         .group_order(&["Project", "Maintenance"])
+    ///         .add_command(cmd!("clean").group("Maintenance"))
+    ///         .add_command(cmd!("init").group("Project"));
+    /// ```
+    pub fn group_order(self, groups: &[&str]) -> Self {
+        let mut re = self;
+        re._group_order = groups.iter().map(|x| x.to_string()).collect();
+        re
+    }
+
+    /// 把另一个 App 挂载为此 App 的子命令.
+    /// `app name ...` 会交给挂载的 App 处理, 就像直接执行那个 App 一样,
+    /// 帮助文档, 示例和示例检查中的程序名称都会变成 `app name`.
+    /// ```
+    ///     use chenbao_cmd::*;
+    ///     let tool = App::new()
+    ///         .app_name("tool")
+    ///         .about("一个内部工具")
+    ///         .add_command(cmd!("build").add_example("tool build", "编译项目"));
+    ///
+    ///     let app = App::new().app_name("umbrella").mount("tool", tool);
+    ///     // umbrella tool build
+    ///     // umbrella tool -h
+    /// ```
+    pub fn mount(self, name: &str, app: App) -> Self {
+        let mut cmd = SubCommand::create_an_sub_command(name).about(&app._about);
+        cmd._mounted_app = Some(Box::new(app));
+
+        self.add_command(cmd)
+    }
+
     /// 自定义帮助信息.
     /// 此方法会替换掉 自动生成的 帮助文档.
     pub fn help_message(self, message: &str) -> Self {
@@ -209,22 +403,83 @@ impl App {
     ///        );
     ///    app.run();
     /// ```
+    /// 执行失败时打印错误信息, 并以非 0 的退出码退出.
     pub fn run(self) {
         let mut re = self;
 
         re._need_to = NeedTo::Run;
 
         let re = re.try_run();
-        match re {
+        match &re {
             DidHandled::Handled => {}
-            DidHandled::Failed(_e) => {
-                eprintln!("{}", _e)
+            DidHandled::Failed(_e) | DidHandled::FailedWithCode(_, _e) => {
+                eprintln!("{}", _e);
+                std::process::exit(re.exit_code());
             }
         }
     }
 
+    /// 依次执行脚本文件中的每一行命令, 最后打印每一行的执行结果.
+    /// * 每一行都是一条命令, 和在 terminal 中输入的一样, 开头的程序名称可以省略.
+    /// * 空行和以 `#` 开头的行会被忽略.
+    /// * `keep_going`: 为 `true` 时某一行执行失败后继续执行后面的行, 为 `false` 时立即停止.
+    ///
+    /// 只要有一行执行失败就返回 `DidHandled::Failed`.
+    /// ```no_run
+    ///     let re = chenbao_cmd::App::new()
+    ///         .app_name("app")
+    ///         .run_script("./runbook.txt", false);
+    /// ```
+    pub fn run_script(self, path: &str, keep_going: bool) -> DidHandled {
+        let content = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(err) => {
+                return DidHandled::Failed(format!(
+                    "{}: {} {}",
+                    "无法读取脚本文件".styled_error_marker(),
+                    path.styled_arg(),
+                    err
+                ))
+            }
+        };
+
+        let re = self.run_script_content(path, &content, keep_going);
+        println!("{}", re.formated_massage());
+
+        if re.is_success() {
+            DidHandled::Handled
+        } else {
+            DidHandled::Failed(format!(
+                "{}: {} 行命令执行失败.",
+                path.styled_arg(),
+                re.failed_count().styled_error_marker()
+            ))
+        }
+    }
+
     /// like run(), but need to handle result.
     pub fn try_run(self) -> DidHandled {
+        if self._need_to.need_confirm() && self.is_yes_env_set() {
+            // 设置了 `<APP_NAME>_YES` 环境变量, 危险的子命令不需要确认.
+            let mut re = self;
+            re._need_to = NeedTo::RunConfirmed;
+            return re.try_run();
+        }
+
+        if let Some(cmd) = self.multi_call_command() {
+            // multi-call 模式, 所有的命令行参数都交给这个子命令.
+            let cmd_args: Vec<String> = self._env_arg[1..].to_vec();
+            return self.dispatch(&cmd, cmd_args.into());
+        }
+
+        if self._env_arg.get(1).map(|x| x.as_str()) == Some("--dry-run") {
+            return self._handle_dry_run();
+        }
+
+        if self._env_arg.get(1).map(|x| x.as_str()) == Some("--yes") {
+            return self._handle_yes();
+        }
+
         let option_string = self._env_arg.get(1);
         match option_string {
             None => {
@@ -236,7 +491,8 @@ impl App {
                     let re = self._handle_app_version();
                     match re {
                         DidHandled::Handled => return re,
-                        DidHandled::Failed(_x) => { /* continue. */ }
+                        DidHandled::Failed(_) | DidHandled::FailedWithCode(..) => { /* continue. */
+                        }
                     }
                 }
 
@@ -244,33 +500,45 @@ impl App {
                     let re = self._handle_app_help();
                     match re {
                         DidHandled::Handled => return re,
-                        DidHandled::Failed(_x) => { /* continue. */ }
+                        DidHandled::Failed(_) | DidHandled::FailedWithCode(..) => { /* continue. */
+                        }
+                    }
+                }
+
+                {
+                    let re = self._handle_app_example();
+                    match re {
+                        DidHandled::Handled => return re,
+                        DidHandled::Failed(_) | DidHandled::FailedWithCode(..) => { /* continue. */
+                        }
                     }
                 }
 
-                // {
-                //     let re = self._handle_app_example();
-                //     match re {
-                //         DidHandled::Handled => return re,
-                //         DidHandled::Failed(_x) => { /* continue. */ }
-                //     }
-                // }
-
-                // {
-                //     let re = self.handle_list_all_command();
-                //     match re {
-                //         DidHandled::Handled => return re,
-                //         DidHandled::Failed(_x) => { /* continue. */ }
-                //     }
-                // }
+                {
+                    let re = self.handle_list_all_command();
+                    match re {
+                        DidHandled::Handled => return re,
+                        DidHandled::Failed(_) | DidHandled::FailedWithCode(..) => { /* continue. */
+                        }
+                    }
+                }
+
+                {
+                    let re = self._handle_shell();
+                    match re {
+                        DidHandled::Handled => return re,
+                        DidHandled::Failed(_) | DidHandled::FailedWithCode(..) => { /* continue. */
+                        }
+                    }
+                }
 
                 {
                     let re = self._handle_commands(command_name);
                     match re {
                         DidHandled::Handled => re,
-                        DidHandled::Failed(_x) => {
+                        DidHandled::Failed(_) | DidHandled::FailedWithCode(..) => {
                             /* 这是最后一个 handle 项目了, 直接返回. */
-                            DidHandled::Failed(_x)
+                            re
                         }
                     }
                 }
@@ -289,28 +557,6 @@ impl App {
             return;
         }
 
-        let mut table = table!();
-        table.set_format(helper::plain_table_formater());
-
-        for x in &self._commands {
-            let short_name = if x._short_name.is_empty() {
-                "".to_string()
-            } else {
-                // ", ".to_string() + &x.short_name
-
-                format!("{}{}", &x._short_name, ", ",)
-            };
-
-            let command_name = &x._cmd_name;
-
-            // TODO: 为 cmd_name 添加颜色.
-            let cmd_name = format!("{}{}", short_name, command_name,);
-
-            table.add_row(row![cmd_name.styled_sub_command(), x._about]);
-        }
-
-        let all_commands_about: String = table.to_string();
-
         let app_usage = format!(
             r#"
 {usg}:
@@ -331,25 +577,40 @@ impl App {
             "-v".styled_sub_command(),
             "--version".styled_sub_command()
         );
-        // let example = format!(
-        //     "{}, {}",
-        //     "-e".styled_sub_command(),
-        //     "--example".styled_sub_command()
-        // );
-        // let list_all_commands = "--list-all-commands".styled_sub_command().to_string();
+        let example = format!(
+            "{}, {}",
+            "-e".styled_sub_command(),
+            "--example".styled_sub_command()
+        );
+        let list_all_commands = "--list-all-commands".styled_sub_command().to_string();
+
+        let shell = "shell".styled_sub_command();
 
         // TODO: 让打印的信息更优美.
-        let flag_message = format!(
-            "{}\n    {help}\t\t显示此命令的帮助.\n    {ver}\t查看此程序的版本.\n",
-            "Flags:".bright_green()
-        );
+        let mut flags: Vec<String> = vec![
+            format!("    {help}\t\t显示此命令的帮助."),
+            format!("    {ver}\t查看此程序的版本."),
+        ];
+        if self.has_examples() {
+            flags.push(format!("    {example}\t查看示例."));
+        }
+        flags.push(format!("    {list_all_commands}\t查看所有 command."));
+        flags.push(format!(
+            "    {} {}\t只解析参数, 打印将要执行的命令, 不执行.",
+            "--dry-run".styled_sub_command(),
+            "<command>".styled_sub_command()
+        ));
+        if self._shell_enabled {
+            flags.push(format!("    {shell}\t\t\t进入交互式 shell."));
+        }
+        let flag_message = format!("{}\n{}\n", "Flags:".bright_green(), flags.join("\n"));
         let author = if self._author.is_empty() {
             "".to_string()
         } else {
             format!("{}: {}\n", "Author".bright_green(), self._author)
         };
 
-        let commands = format!("{}\n{}", "Commands:".bright_green(), all_commands_about);
+        let commands = self.formated_commands();
 
         println!(
             r#"
@@ -364,118 +625,730 @@ impl App {
         );
     }
 
-    // /// 打印 App 的示例.
-    // /// `app -e` 时调用此函数.
-    // pub fn print_app_examples(&self) {
-    //     if self._app_examples.is_empty() {
-    //         //  自动生成的示例效果不好, 先不自动生成.
-
-    //         // let mut table = table!();
-    //         // table.set_format(helper::plain_table_formater());
-
-    //         // for x in &self._commands {
-    //         //     let rows = x.formated_command_example(&self._app_name);
+    /// 打印所有子命令, 包括 short_name, 参数类型, 介绍和示例的数量.
+    /// `app --list-all-commands` 时调用此函数.
+    pub fn print_all_commands(&self) {
+        println!("{}", self.formated_all_commands());
+    }
 
-    //         //     for x in rows {
-    //         //         table.add_row(x);
-    //         //     }
-    //         // }
-    //         // println!("{}", table);
-    //         // table.printstd();
-    //     } else {
-    //         println!("{}", vec_row_to_table(self._app_examples.pretty_formated()));
-    //     }
-    // }
+    /// 打印 App 的示例.
+    /// `app -e` 时调用此函数.
+    /// 如果没有使用 `add_app_example()` 添加示例, 则打印所有子命令的示例.
+    pub fn print_app_examples(&self) {
+        if self._app_examples.is_empty() {
+            for x in self._commands.iter().filter(|x| !x._hidden) {
+                match &x._mounted_app {
+                    Some(app) => app
+                        .renamed(&format!("{} {}", self._app_name, x._cmd_name))
+                        .print_app_examples(),
+                    None => x.print_command_example(&self._app_name),
+                }
+            }
+        } else {
+            println!("{}", vec_row_to_table(self._app_examples.pretty_formated()));
+        }
+    }
 }
 
 impl App {
     // -------- Private Part --------
 
-    fn _handle_defalt_implement(&self) {}
+    fn _handle_defalt_implement(&self) {}
+
+    /// `app -h` 中的 Commands 部分.
+    /// 没有分组的子命令显示在 `Commands:` 下面,
+    /// 有分组的子命令按照分组显示在各自的标题下面.
+    fn formated_commands(&self) -> String {
+        let mut sections: Vec<String> = vec![];
+
+        let ungrouped: Vec<&SubCommand> = self
+            ._commands
+            .iter()
+            .filter(|x| !x._hidden && x._group.is_empty())
+            .collect();
+
+        if !ungrouped.is_empty() {
+            sections.push(format!(
+                "{}\n{}",
+                "Commands:".bright_green(),
+                Self::formated_commands_table(&ungrouped)
+            ));
+        }
+
+        for group in self.group_names() {
+            let cmds: Vec<&SubCommand> = self
+                ._commands
+                .iter()
+                .filter(|x| !x._hidden && x._group == group)
+                .collect();
+
+            if !cmds.is_empty() {
+                sections.push(format!(
+                    "{}\n{}",
+                    format!("{}:", group).bright_green(),
+                    Self::formated_commands_table(&cmds)
+                ));
+            }
+        }
+
+        if let Some(plugins) = self.formated_plugins() {
+            sections.push(plugins);
+        }
+
+        sections.join("\n")
+    }
+
+    /// `app --list-all-commands` 的内容, 外部子命令在最后的 `Plugins:` 下面.
+    fn formated_all_commands(&self) -> String {
+        let mut table = Table::new();
+        table.set_format(plain_table_formater());
+
+        self.listed_commands().iter().for_each(|(prefix, x)| {
+            x.formated_row_in_list_all_command(prefix)
+                .iter()
+                .for_each(|x| {
+                    table.add_row(x.clone());
+                });
+        });
+
+        match self.formated_plugins() {
+            Some(plugins) => format!("{}\n{}", table, plugins),
+            None => table.to_string(),
+        }
+    }
+
+    /// `app -h` 和 `app --list-all-commands` 中的 `Plugins:` 部分, 没有外部子命令时返回 `None`.
+    fn formated_plugins(&self) -> Option<String> {
+        let plugins = self.plugins();
+        if plugins.is_empty() {
+            return None;
+        }
+
+        let mut table = table!();
+        table.set_format(helper::plain_table_formater());
+        for x in &plugins {
+            table.add_row(row![x.name.styled_sub_command(), x.path.display()]);
+        }
+
+        Some(format!("{}\n{}", "Plugins:".bright_green(), table))
+    }
+
+    /// 所有分组的名称.
+    /// 先是 `App::group_order()` 中设置的顺序, 然后是其他分组第一次出现的顺序.
+    fn group_names(&self) -> Vec<String> {
+        let mut re: Vec<String> = self._group_order.clone();
+
+        for x in &self._commands {
+            if !x._group.is_empty() && !re.contains(&x._group) {
+                re.push(x._group.clone());
+            }
+        }
+
+        re
+    }
+
+    /// 子命令的表格.
+    fn formated_commands_table(cmds: &[&SubCommand]) -> String {
+        let mut table = table!();
+        table.set_format(helper::plain_table_formater());
+
+        for x in cmds {
+            let short_name = if x._short_name.is_empty() {
+                "".to_string()
+            } else {
+                // ", ".to_string() + &x.short_name
+
+                format!("{}{}", &x._short_name, ", ",)
+            };
+
+            let command_name = &x._cmd_name;
+
+            // TODO: 为 cmd_name 添加颜色.
+            let cmd_name = format!("{}{}", short_name, command_name,);
+
+            table.add_row(row![cmd_name.styled_sub_command(), x._about]);
+        }
+
+        table.to_string()
+    }
+
+    /// app help 的默认实现;
+    /// // -h --help -v -version
+    fn _handle_app_help(&self) -> DidHandled {
+        let command_name = &*self._env_arg[1];
+
+        if ["-h", "--help"].contains(&command_name) {
+            if self._need_to.is_run() {
+                self.print_app_help(); // 打印 App 的帮助信息.
+            }
+            DidHandled::Handled
+        } else {
+            DidHandled::Failed(r#"不是 "-h" or "--help""#.to_string())
+        }
+    }
+
+    /// app version 命令的默认实现
+    fn _handle_app_version(&self) -> DidHandled {
+        // 处理 App 的flags.
+        //  -v -version
+
+        let command_name = &*self._env_arg[1];
+        if ["-v", "--version"].contains(&command_name) {
+            if self._need_to.is_run() {
+                println!("{}", self._app_version_message);
+            }
+            DidHandled::Handled
+        } else {
+            DidHandled::Failed("不是 version 命令".to_string())
+        }
+    }
+
+    /// `app --list-all-commands` 的默认实现.
+    /// `app --list-all-commands toml` 会输出 toml 格式, 方便其他程序读取.
+    fn handle_list_all_command(&self) -> DidHandled {
+        let command_name = &self._env_arg[1];
+
+        if command_name == "--list-all-commands" {
+            match self._commands_arg.first().map(|x| x.as_str()) {
+                None => {
+                    if self._need_to.is_run() {
+                        self.print_all_commands();
+                    }
+                }
+                Some("toml") if self._commands_arg.len() == 1 => {
+                    let toml_str = self.formated_all_commands_toml();
+                    match toml_str {
+                        Ok(toml_str) => {
+                            if self._need_to.is_run() {
+                                println!("{}", toml_str);
+                            }
+                        }
+                        Err(err) => return DidHandled::Failed(err),
+                    }
+                }
+                Some(_) => {
+                    return DidHandled::Failed(format!(
+                        "{}: {} 只接受 {} 一个参数, 实际接收到的: {:?}",
+                        "参数错误".styled_error_marker(),
+                        "--list-all-commands".styled_sub_command(),
+                        "toml".styled_arg(),
+                        self._commands_arg,
+                    ));
+                }
+            }
+
+            DidHandled::Handled
+        } else {
+            DidHandled::Failed(format!(
+                "不是 {} 命令",
+                "--list-all-commands".styled_sub_command(),
+            ))
+        }
+    }
+
+    /// 所有没有隐藏的子命令, toml 格式.
+    fn formated_all_commands_toml(&self) -> Result<String, String> {
+        #[derive(serde::Serialize)]
+        struct AllCommands {
+            app_name: String,
+            commands: Vec<CommandInfo>,
+            plugins: Vec<Plugin>,
+        }
+
+        let all = AllCommands {
+            app_name: self._app_name.clone(),
+            commands: self
+                .listed_commands()
+                .iter()
+                .map(|(prefix, x)| x.command_info(prefix))
+                .collect(),
+            plugins: self.plugins(),
+        };
+
+        toml::to_string_pretty(&all).map_err(|e| e.to_string())
+    }
+
+    /// 处理只输入了程序名称没有子命令也没有任何 flag 的情况.
+    fn _handle_app_default_acton(&self) -> DidHandled {
+        {
+            if self._env_arg.len() == 1 {
+                match &self._app_default_action {
+                    AppDefaultAction::PrintHelpMessage => {
+                        if self._need_to.is_run() {
+                            self.print_app_help();
+                        }
+
+                        return DidHandled::Handled;
+                    }
+                    AppDefaultAction::CustomAction(f) => {
+                        if self._need_to.is_run() {
+                            f();
+                        }
+
+                        return DidHandled::Handled;
+                    }
+                    AppDefaultAction::PickCommand => {
+                        if !self._need_to.is_run() {
+                            return DidHandled::Handled;
+                        }
+
+                        return self.pick_command();
+                    }
+                }
+            };
+        }
+        // return DidHandled::Failed("有子命令或者 flag, 不是 app_default_acton".to_string());
+        DidHandled::Failed("有子命令或者 flag, 不是 app_default_acton".to_string())
+    }
+
+    /// multi-call 模式下, `argv[0]` 对应的子命令.
+    /// 没有启用 multi-call 模式, 或者 `argv[0]` 不是子命令的名称时返回 None.
+    fn multi_call_command(&self) -> Option<SubCommand> {
+        if !self._multi_call {
+            return None;
+        }
+
+        let invoked_name = self
+            ._env_arg
+            .first()
+            .and_then(|x| std::path::Path::new(x).file_name())
+            .map(|x| x.to_string_lossy().into_owned())?;
+
+        if invoked_name == self._app_name {
+            return None;
+        }
+
+        let mut cmd = self
+            ._commands
+            .iter()
+            .find(|x| invoked_name == x._cmd_name || invoked_name == x._short_name)?
+            .clone();
+        cmd._invoked_as = Some(invoked_name);
+
+        Some(cmd)
+    }
+
+    /// 执行子命令, 并在前后调用 App 的 hook 和 middleware.
+    fn dispatch(&self, cmd: &SubCommand, cmd_args: SharedVecString) -> DidHandled {
+        if let Some(app) = &cmd._mounted_app {
+            // 此 App 的 hook 也会作用于挂载的 App 中的子命令.
+            let mut app = app.as_ref().clone();
+            app._before_hooks = [self._before_hooks.clone(), app._before_hooks].concat();
+            app._after_hooks = [app._after_hooks, self._after_hooks.clone()].concat();
+            app._middlewares = [self._middlewares.clone(), app._middlewares].concat();
+
+            let prefix = format!("{} {}", self._app_name, cmd._cmd_name);
+            return app.mounted_try_run(&prefix, cmd_args, self._need_to);
+        }
+
+        let cmd_args = cmd.args_with_answers_env(&self._app_name, cmd_args);
+        let need_hooks = self._need_to.is_run() && !hook::is_command_flag(cmd_args.first());
+
+        if need_hooks {
+            if let Err(err) = hook::run_hooks(&self._before_hooks, &cmd._cmd_name, &cmd_args) {
+                return DidHandled::Failed(err);
+            }
+        }
+
+        let run = || cmd.sub_command_try_run(&self._app_name, cmd_args.clone(), self._need_to);
+
+        let re = if need_hooks {
+            let invocation = Invocation {
+                app_name: &self._app_name,
+                cmd_name: &cmd._cmd_name,
+                args: &cmd_args,
+            };
+            hook::run_middlewares(&self._middlewares, &invocation, &run)
+        } else {
+            run()
+        };
+
+        if need_hooks {
+            let after = hook::run_hooks(&self._after_hooks, &cmd._cmd_name, &cmd_args);
+            if let (DidHandled::Handled, Err(err)) = (&re, after) {
+                return DidHandled::Failed(err);
+            }
+        }
+
+        re
+    }
+
+    /// 执行外部子命令, 剩下的参数都会交给它.
+    fn _handle_plugin(&self, path: &std::path::Path) -> DidHandled {
+        if self._need_to.is_dry_run() {
+            println!(
+                "{} {} {}",
+                "dry run:".bright_yellow(),
+                path.display().styled_sub_command(),
+                format!("{:?}", self._commands_arg).styled_arg()
+            );
+        }
+
+        if !self._need_to.is_run() {
+            return DidHandled::Handled;
+        }
+
+        let status = std::process::Command::new(path)
+            .args(self._commands_arg.iter())
+            .status();
+
+        match status {
+            Ok(status) if status.success() => DidHandled::Handled,
+            Ok(status) => {
+                let code = status.code().unwrap_or(1);
+                DidHandled::FailedWithCode(
+                    code,
+                    format!(
+                        "{}: {} exit code: {}",
+                        "外部子命令执行失败".styled_error_marker(),
+                        path.display().styled_arg(),
+                        code
+                    ),
+                )
+            }
+            Err(err) => DidHandled::Failed(format!(
+                "{}: {} {}",
+                "无法执行外部子命令".styled_error_marker(),
+                path.display().styled_arg(),
+                err
+            )),
+        }
+    }
+
+    /// 查找外部子命令的目录.
+    fn plugin_dirs(&self) -> Vec<std::path::PathBuf> {
+        if self._plugin_dirs.is_empty() {
+            plugin::path_dirs()
+        } else {
+            self._plugin_dirs.clone()
+        }
+    }
+
+    /// PATH 中的外部子命令, 不包括和子命令同名的.
+    /// 没有启用外部子命令时返回空的 Vec.
+    fn plugins(&self) -> Vec<Plugin> {
+        if !self._plugins_enabled {
+            return vec![];
+        }
+
+        plugin::discover_plugins(&self.plugin_dirs(), &self._app_name)
+            .into_iter()
+            .filter(|p| {
+                !self
+                    ._commands
+                    .iter()
+                    .any(|x| x._cmd_name == p.name || x._short_name == p.name)
+            })
+            .collect()
+    }
+
+    /// `app --dry-run <command> [arguments]` 的默认实现.
+    /// 解析参数并打印将要执行的子命令和参数, 不执行 action.
+    fn _handle_dry_run(self) -> DidHandled {
+        self.run_without_global_flag("--dry-run", NeedTo::DryRun)
+    }
+
+    /// `app --yes <command> [arguments]` 的默认实现.
+    /// 执行子命令, 危险的子命令不需要用户确认.
+    fn _handle_yes(self) -> DidHandled {
+        self.run_without_global_flag("--yes", NeedTo::RunConfirmed)
+    }
+
+    /// 移除 `app <flag> <command> [arguments]` 中的全局 flag, 然后使用 `need_to` 执行子命令.
+    /// 只解析不执行时依然只解析.
+    fn run_without_global_flag(self, flag: &str, need_to: NeedTo) -> DidHandled {
+        let mut re = self;
+
+        let mut env_arg: Vec<String> = re._env_arg.to_vec();
+        env_arg.remove(1); // 移除 flag
+
+        if env_arg.len() < 2 {
+            return DidHandled::Failed(format!(
+                "{}: {} 后面需要一个子命令, 示例: {} {} {}",
+                "参数数量错误".styled_error_marker(),
+                flag.styled_sub_command(),
+                re._app_name.styled_sub_command(),
+                flag.styled_sub_command(),
+                "<command>".styled_sub_command(),
+            ));
+        }
+
+        re._commands_arg = if env_arg.len() > 2 {
+            env_arg[2..].to_vec().into()
+        } else {
+            vec![].into()
+        };
+        re._env_arg = env_arg.into();
+        if re._need_to.is_run() {
+            re._need_to = need_to;
+        }
+
+        re.try_run()
+    }
+
+    /// 是否设置了跳过危险子命令确认的环境变量 `<APP_NAME>_YES`.
+    /// 挂载的 App 使用最外层 App 的名称.
+    fn is_yes_env_set(&self) -> bool {
+        let root_name = root_app_name(&self._app_name);
+
+        match std::env::var(yes_env_var(root_name)) {
+            Ok(x) => !x.is_empty() && x != "0" && x.to_lowercase() != "false",
+            Err(_e) => false,
+        }
+    }
+
+    /// `app shell` 的默认实现.
+    fn _handle_shell(&self) -> DidHandled {
+        let command_name = &*self._env_arg[1];
+
+        if self._shell_enabled && command_name == "shell" && self._commands_arg.is_empty() {
+            if self._need_to.is_run() {
+                self.run_shell();
+            }
+
+            DidHandled::Handled
+        } else {
+            DidHandled::Failed(r#"不是 "shell""#.to_string())
+        }
+    }
+
+    /// 交互式 shell, 直到用户输入 `exit` 或者发生错误.
+    fn run_shell(&self) {
+        use crate::shell::{FileHistory, ShellCompletion};
+
+        let theme = dialoguer::theme::ColorfulTheme::default();
+        let mut history = FileHistory::load(self.shell_history_path());
+        let completion = ShellCompletion::new(&self._commands);
+
+        println!(
+            "输入 {} 查看所有命令, 输入 {} 退出.",
+            "help".styled_sub_command(),
+            "exit".styled_sub_command()
+        );
+
+        loop {
+            let re = dialoguer::Input::<String>::with_theme(&theme)
+                .with_prompt(&self._app_name)
+                .allow_empty(true)
+                .history_with(&mut history)
+                .completion_with(&completion)
+                .interact_text();
+
+            let line = match re {
+                Ok(line) => line,
+                Err(err) => {
+                    eprintln!("{}", err.red());
+                    return;
+                }
+            };
+
+            let args = helper::parse_arg_string(&line);
+            match args.first().map(|x| x.as_str()) {
+                None => continue,
+                Some("exit") | Some("quit") => return,
+                Some("help") => {
+                    self.print_app_help();
+                    continue;
+                }
+                Some("shell") => {
+                    eprintln!("已经在 {} 中了.", "shell".styled_sub_command());
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            if let DidHandled::Failed(err) | DidHandled::FailedWithCode(_, err) =
+                self.try_run_args(args)
+            {
+                eprintln!("{}", err);
+            }
+        }
+    }
+
+    /// 作为挂载的 App 执行.
+    /// * `prefix`: 挂载后的程序名称, 例如 `"umbrella tool"`.
+    pub(crate) fn mounted_try_run(
+        &self,
+        prefix: &str,
+        cmd_args: SharedVecString,
+        need_to: NeedTo,
+    ) -> DidHandled {
+        let mut re = self.renamed(prefix);
+
+        re._commands_arg = if cmd_args.len() > 1 {
+            cmd_args[1..].to_vec().into()
+        } else {
+            vec![].into()
+        };
+        re._env_arg = std::iter::once(prefix.to_string())
+            .chain(cmd_args.iter().cloned())
+            .collect::<Vec<String>>()
+            .into();
+        re._need_to = need_to;
+
+        re.try_run()
+    }
+
+    /// 修改程序名称, 示例中的程序名称也会一起修改.
+    fn renamed(&self, app_name: &str) -> App {
+        let mut re = self.clone();
+        let old_name = &self._app_name;
+
+        let rename = |examples: &mut Examples| {
+            for x in &mut examples.val {
+                if x.command == *old_name {
+                    x.command = app_name.to_string();
+                } else if let Some(rest) = x.command.strip_prefix(&format!("{} ", old_name)) {
+                    x.command = format!("{} {}", app_name, rest);
+                }
+            }
+        };
+
+        rename(&mut re._app_examples);
+        for cmd in &mut re._commands {
+            rename(&mut cmd._exaples);
+        }
+        re._app_name = app_name.to_string();
+
+        re
+    }
+
+    /// 挂载的 App 和挂载的名称.
+    fn mounted_apps(&self) -> Vec<(String, App)> {
+        self._commands
+            .iter()
+            .filter_map(|x| {
+                x._mounted_app.as_ref().map(|app| {
+                    let prefix = format!("{} {}", self._app_name, x._cmd_name);
+                    (prefix.clone(), app.renamed(&prefix))
+                })
+            })
+            .collect()
+    }
+
+    /// 所有没有隐藏的子命令, 包括挂载的 App 中的子命令.
+    /// 返回的 String 是挂载的 App 中的子命令需要加上的前缀, 例如 `"tool "`.
+    fn listed_commands(&self) -> Vec<(String, &SubCommand)> {
+        let mut re: Vec<(String, &SubCommand)> = vec![];
+
+        for x in self._commands.iter().filter(|x| !x._hidden) {
+            re.push((String::new(), x));
+
+            if let Some(app) = &x._mounted_app {
+                for (prefix, cmd) in app.listed_commands() {
+                    re.push((format!("{} {}", x._cmd_name, prefix), cmd));
+                }
+            }
+        }
+
+        re
+    }
+
+    /// 用 `args` 代替命令行参数执行一次 try_run(), `args` 不包括程序名称.
+    fn try_run_args(&self, args: Vec<String>) -> DidHandled {
+        let mut re = self.clone();
+
+        re._commands_arg = if args.len() > 1 {
+            args[1..].to_vec().into()
+        } else {
+            vec![].into()
+        };
+        re._env_arg = std::iter::once(self._app_name.clone())
+            .chain(args)
+            .collect::<Vec<String>>()
+            .into();
+
+        re.try_run()
+    }
 
-    /// app help 的默认实现;
-    /// // -h --help -v -version
-    fn _handle_app_help(&self) -> DidHandled {
-        let command_name = &*self._env_arg[1];
+    /// 执行脚本内容中的每一行命令.
+    fn run_script_content(
+        &self,
+        script_name: &str,
+        content: &str,
+        keep_going: bool,
+    ) -> ScriptResult {
+        let lines = script::parse_script(content);
+        let mut re = ScriptResult {
+            script_name: script_name.to_string(),
+            results: vec![],
+            skipped: 0,
+        };
 
-        if ["-h", "--help"].contains(&command_name) {
-            if self._need_to.is_run() {
-                self.print_app_help(); // 打印 App 的帮助信息.
+        for (i, line) in lines.iter().enumerate() {
+            let mut args = helper::parse_arg_string(&line.command);
+            if args.first() == Some(&self._app_name) {
+                args.remove(0); // 移除 app name
             }
-            DidHandled::Handled
-        } else {
-            DidHandled::Failed(r#"不是 "-h" or "--help""#.to_string())
-        }
-    }
 
-    /// app version 命令的默认实现
-    fn _handle_app_version(&self) -> DidHandled {
-        // 处理 App 的flags.
-        //  -v -version
+            let result = self.try_run_args(args);
+            let failed = result != DidHandled::Handled;
 
-        let command_name = &*self._env_arg[1];
-        if ["-v", "--version"].contains(&command_name) {
-            if self._need_to.is_run() {
-                println!("{}", self._app_version_message);
+            re.results.push(ScriptLineResult {
+                line: line.clone(),
+                result,
+            });
+
+            if failed && !keep_going {
+                re.skipped = lines.len() - i - 1;
+                break;
             }
-            DidHandled::Handled
-        } else {
-            DidHandled::Failed("不是 version 命令".to_string())
         }
+
+        re
     }
 
-    // fn handle_list_all_command(&self) -> DidHandled {
-    //     let command_name = &self._env_arg[1];
+    /// `app shell` 的 history 文件的路径.
+    fn shell_history_path(&self) -> Option<std::path::PathBuf> {
+        if let Some(path) = &self._shell_history_file {
+            return Some(path.clone());
+        }
 
-    //     if command_name == "--list-all-commands" {
-    //         {
-    //             // print all commands
-    //             let mut table = Table::new();
-    //             table.set_format(plain_table_formater());
+        std::env::var_os("HOME")
+            .map(|home| std::path::Path::new(&home).join(format!(".{}_history", self._app_name)))
+    }
 
-    //             self._commands.iter().for_each(|x| {
-    //                 x.formated_row_in_list_all_command().iter().for_each(|x| {
-    //                     table.add_row(x.clone());
-    //                 });
-    //             });
-    //             if self._need_to.is_run() {
-    //                 println!("{}", table);
-    //             }
-    //         }
+    /// 让用户从列表中选择一个子命令并输入参数, 然后执行这个子命令.
+    fn pick_command(&self) -> DidHandled {
+        let cmds: Vec<&SubCommand> = self._commands.iter().filter(|x| !x._hidden).collect();
 
-    //         DidHandled::Handled
-    //     } else {
-    //         DidHandled::Failed(format!(
-    //             "不是 {} 命令",
-    //             "--list-all-commands".styled_sub_command(),
-    //         ))
-    //     }
-    // }
+        if !crate::question_and_anser::is_interactive() || cmds.is_empty() {
+            self.print_app_help();
+            return DidHandled::Handled;
+        }
 
-    /// 处理只输入了程序名称没有子命令也没有任何 flag 的情况.
-    fn _handle_app_default_acton(&self) -> DidHandled {
-        {
-            if self._env_arg.len() == 1 {
-                match &self._app_default_action {
-                    AppDefaultAction::PrintHelpMessage => {
-                        if self._need_to.is_run() {
-                            self.print_app_help();
-                        }
+        let items: Vec<String> = cmds
+            .iter()
+            .map(|x| {
+                if x._about.is_empty() {
+                    x._cmd_name.clone()
+                } else {
+                    format!("{}  {}", x._cmd_name, x._about)
+                }
+            })
+            .collect();
 
-                        return DidHandled::Handled;
-                    }
-                    AppDefaultAction::CustomAction(f) => {
-                        if self._need_to.is_run() {
-                            f();
-                        }
+        let re = dialoguer::FuzzySelect::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .with_prompt("选择一个命令")
+            .items(&items)
+            .default(0)
+            .interact_opt();
 
-                        return DidHandled::Handled;
-                    }
+        match re {
+            Ok(Some(index)) => {
+                let cmd = cmds[index];
+                match cmd.ask_for_args() {
+                    Ok(cmd_args) => self.dispatch(cmd, cmd_args.into()),
+                    Err(err) => DidHandled::Failed(err),
                 }
-            };
+            }
+            Ok(None) => DidHandled::Handled, // 用户取消了选择.
+            Err(err) => {
+                eprintln!("{}", err.red());
+                self.print_app_help();
+                DidHandled::Handled
+            }
         }
-        // return DidHandled::Failed("有子命令或者 flag, 不是 app_default_acton".to_string());
-        DidHandled::Failed("有子命令或者 flag, 不是 app_default_acton".to_string())
     }
 
     // fn _handle_commands(&self, command_name: &String) -> DidHandled {
@@ -485,38 +1358,56 @@ impl App {
                 if command_name == &x._cmd_name || command_name == &x._short_name {
                     let cmd_args = self._commands_arg.clone();
 
-                    return x.sub_command_try_run(&self._app_name, cmd_args, self._need_to);
+                    return self.dispatch(x, cmd_args);
                 } else {
                     continue;
                 }
             }
         }
 
-        // DidHandled::Failed(format!(
-        //     "未知命令: {}\n\n输入 {} {} 查看所有命令",
-        //     self._env_arg.join(" ").styled_sub_command(),
-        //     self._app_name.styled_sub_command(),
-        //     "--list-all-commands".styled_sub_command(),
-        // ))
+        if self._plugins_enabled {
+            if let Some(path) =
+                plugin::find_plugin(&self.plugin_dirs(), &self._app_name, command_name)
+            {
+                return self._handle_plugin(&path);
+            }
+        }
+
         DidHandled::Failed(format!(
-            "未知命令: {}\n",
+            "未知命令: {}\n\n输入 {} {} 查看所有命令",
             self._env_arg.join(" ").styled_sub_command(),
+            self._app_name.styled_sub_command(),
+            "--list-all-commands".styled_sub_command(),
         ))
     }
 
-    // fn _handle_app_example(&self) -> DidHandled {
-    //     let command_name = self._env_arg[1].clone();
+    /// app example 的默认实现
+    /// -e --example
+    fn _handle_app_example(&self) -> DidHandled {
+        let command_name = &*self._env_arg[1];
+
+        if ["-e", "--example"].contains(&command_name) {
+            if self._need_to.is_run() {
+                self.print_app_examples();
+            }
 
-    //     if command_name == "-e" || command_name == "--example" {
-    //         if self._need_to.is_run() {
-    //             self.print_app_examples();
-    //         }
+            DidHandled::Handled
+        } else {
+            DidHandled::Failed(r#"不是 "-e" or "--example""#.to_string())
+        }
+    }
 
-    //         DidHandled::Handled
-    //     } else {
-    //         DidHandled::Failed("不是 version 命令".to_string())
-    //     }
-    // }
+    /// app 自己或者任意一个没有隐藏的子命令有示例.
+    fn has_examples(&self) -> bool {
+        !self._app_examples.is_empty()
+            || self._commands.iter().any(|x| {
+                !x._hidden
+                    && (!x._exaples.is_empty()
+                        || x._mounted_app
+                            .as_ref()
+                            .is_some_and(|app| app.has_examples()))
+            })
+    }
 
     fn _formated_help(&self) -> String {
         if self._help_message.trim() != "" {
@@ -524,28 +1415,6 @@ impl App {
             println!("{}", self._help_message);
         }
 
-        let mut table = table!();
-        table.set_format(helper::plain_table_formater());
-
-        for x in &self._commands {
-            let short_name = if x._short_name.is_empty() {
-                "".to_string()
-            } else {
-                // ", ".to_string() + &x.short_name
-
-                format!("{}{}", &x._short_name, ", ",)
-            };
-
-            let command_name = &x._cmd_name;
-
-            // TODO: 为 cmd_name 添加颜色.
-            let cmd_name = format!("{}{}", short_name, command_name,);
-
-            table.add_row(row![cmd_name.styled_sub_command(), x._about]);
-        }
-
-        let all_commands_about: String = table.to_string();
-
         let app_usage = format!(
             r#"
 {usg}:
@@ -581,7 +1450,7 @@ impl App {
             format!("{}: {}\n", "Author".bright_green(), self._author)
         };
 
-        let commands = format!("{}\n{}", "Commands:".bright_green(), all_commands_about);
+        let commands = self.formated_commands();
 
         format!(
             r#"
@@ -657,13 +1526,13 @@ impl App {
 
         match did_handled {
             DidHandled::Handled => { /* runs perfact. */ }
-            DidHandled::Failed(err_message) => {
+            DidHandled::Failed(err_message) | DidHandled::FailedWithCode(_, err_message) => {
                 println!("{}", err_message);
             }
         }
 
         // 返回未修改的 self
-        return self;
+        self
     }
 
     /// 检查子命令示example是否能正确的被解析
@@ -684,19 +1553,19 @@ impl App {
             }
 
             {
-                let re = self.debug_example_check();
+                let re = self.debug_example_messages();
                 if !re.is_empty() {
                     println!(
                         "\n{}\n",
                         "开始检查 example 是否能被解析".bright_yellow().bold()
                     );
                     for x in re {
-                        print!("{}", x.formated_massage());
+                        print!("{}", x);
                     }
                 }
             }
         }
-        return self;
+        self
     }
 
     fn debug_duplicate_names_check(&self) -> Vec<ErrorTable> {
@@ -761,8 +1630,15 @@ impl App {
             default_impls.insert("--help");
             default_impls.insert("-v");
             default_impls.insert("--version");
-            // default_impls.insert("-e");
-            // default_impls.insert("--example");
+            default_impls.insert("-e");
+            default_impls.insert("--example");
+            default_impls.insert("--list-all-commands");
+            default_impls.insert("--dry-run");
+            default_impls.insert("--yes");
+
+            if self._shell_enabled {
+                default_impls.insert("shell");
+            }
         }
         for x in &self._commands {
             {
@@ -796,11 +1672,35 @@ impl App {
         }
     }
 
-    /// 检查所有 子命令 的示例是否能被解析.
+    /// 检查示例是否能被解析, 包括挂载的 App 的示例.
+    fn debug_example_messages(&self) -> Vec<String> {
+        let mut re: Vec<String> = self
+            .debug_example_check()
+            .iter()
+            .map(|x| x.formated_massage())
+            .collect();
+
+        for (_prefix, app) in self.mounted_apps() {
+            re.append(&mut app.debug_example_messages());
+        }
+
+        re
+    }
+
+    /// 检查 app 的示例和所有 子命令 的示例是否能被解析.
     fn debug_example_check<'a>(&'a self) -> Vec<ExampleTestResult<'a>> {
         let mut ok: Vec<ExampleTestResult<'a>> = vec![];
         let mut err: Vec<ExampleTestResult<'a>> = vec![];
 
+        if !self._app_examples.is_empty() {
+            let r = self.debug_app_example_check();
+            if r.is_success() {
+                ok.push(r);
+            } else {
+                err.push(r);
+            }
+        }
+
         self._commands.iter().for_each(|cmd| {
             let r = cmd.debug_cmd_example_check(&self._app_name);
             if r.is_success() {
@@ -812,7 +1712,51 @@ impl App {
 
         ok.append(&mut err);
 
-        return ok;
+        ok
+    }
+
+    /// 检查 `App::add_app_example()` 添加的示例是否能被解析.
+    fn debug_app_example_check(&self) -> ExampleTestResult<'_> {
+        let mut result = ExampleTestResult::new(&self._app_name);
+
+        for exam in &self._app_examples.val {
+            let mut err_msg: Vec<String> = vec![];
+
+            let mut virtual_env_args = helper::parse_arg_string(&exam.command);
+            if !virtual_env_args.is_empty() {
+                // 移除 app name
+                if let Err(name) = helper::remove_app_name(&mut virtual_env_args, &self._app_name) {
+                    err_msg.push(format!(
+                        "{}: 需要 {}; 实际收到的: {:?}",
+                        "程序名称错误".bright_red(),
+                        self._app_name.styled_sub_command(),
+                        name
+                    ));
+                }
+            }
+
+            if !virtual_env_args.is_empty() {
+                // 和用户输入的一样解析, 包括 App 的 flag 和外部子命令, 但是不执行.
+                let mut app = self.clone();
+                app._need_to = NeedTo::ParseOnly;
+
+                let re = app.try_run_args(virtual_env_args);
+                if let DidHandled::Failed(msg) | DidHandled::FailedWithCode(_, msg) = re {
+                    err_msg.push(msg);
+                }
+            }
+
+            if err_msg.is_empty() {
+                result.success_examples.push(exam);
+            } else {
+                result.failures_examples.push(Sadadsf {
+                    base: exam,
+                    err_msg,
+                });
+            }
+        }
+
+        result
     }
 }
 
@@ -849,11 +1793,20 @@ impl Default for App {
             _app_version_message: "0.0.1".to_owned(),
             _help_message: Default::default(),
             _commands: Default::default(),
+            _group_order: Default::default(),
             _env_arg: env_args.into(),
             _app_examples: Examples::new(),
             _commands_arg: sub_cmd_arg.into(),
             _app_default_action: Default::default(),
             _need_to: NeedTo::Run,
+            _before_hooks: vec![],
+            _after_hooks: vec![],
+            _middlewares: vec![],
+            _multi_call: false,
+            _plugins_enabled: false,
+            _plugin_dirs: vec![],
+            _shell_enabled: false,
+            _shell_history_file: None,
         }
     }
 }
@@ -865,6 +1818,14 @@ pub(crate) enum NeedTo {
 
     /// 只解析, 不执行.
     ParseOnly,
+
+    /// 解析并打印将要执行的子命令和参数, 不执行.
+    /// `app --dry-run <command> [arguments]`
+    DryRun,
+
+    /// 执行设置的 ArgAction, 危险的子命令不需要用户确认.
+    /// `app --yes <command> [arguments]`
+    RunConfirmed,
 }
 
 impl NeedTo {
@@ -873,6 +1834,536 @@ impl NeedTo {
         match self {
             NeedTo::Run => true,
             NeedTo::ParseOnly => false,
+            NeedTo::DryRun => false,
+            NeedTo::RunConfirmed => true,
+        }
+    }
+
+    /// 危险的子命令是否需要用户确认.
+    pub fn need_confirm(&self) -> bool {
+        matches!(self, NeedTo::Run)
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        matches!(self, NeedTo::DryRun)
+    }
+}
+
+#[cfg(test)]
+mod test_app {
+    use super::*;
+
+    /// 模拟用户输入, 返回 try_run() 的结果.
+    fn try_run_with(app: &App, virtual_env_args: &[&str]) -> DidHandled {
+        let mut re = app.clone();
+        let env_arg: Vec<String> = virtual_env_args.iter().map(|x| x.to_string()).collect();
+
+        re._commands_arg = if env_arg.len() > 2 {
+            env_arg[2..].to_vec().into()
+        } else {
+            vec![].into()
+        };
+        re._env_arg = env_arg.into();
+        re._need_to = NeedTo::ParseOnly;
+
+        re.try_run()
+    }
+
+    /// 模拟用户输入并真正执行, 返回 try_run_args() 的结果.
+    fn run_with(app: &App, args: &[&str]) -> DidHandled {
+        let mut re = app.clone();
+        re._need_to = NeedTo::Run;
+
+        re.try_run_args(args.iter().map(|x| x.to_string()).collect())
+    }
+
+    #[test]
+    fn hidden_command_not_in_help() {
+        let app = App::new()
+            .add_command(SubCommand::create_an_sub_command("build").about("编译项目"))
+            .add_command(
+                SubCommand::create_an_sub_command("compile")
+                    .hidden(true)
+                    .deprecated("build", ""),
+            );
+
+        let table = app.formated_commands();
+        assert!(table.contains("build"));
+        assert!(!table.contains("compile"));
+
+        // 隐藏的子命令依然可以被执行.
+        assert_eq!(try_run_with(&app, &["app", "compile"]), DidHandled::Handled);
+        assert_eq!(
+            try_run_with(&app, &["app", "compile", "-h"]),
+            DidHandled::Handled
+        );
+    }
+
+    #[test]
+    fn app_example_check() {
+        let app = App::new()
+            .app_name("app")
+            .add_command(
+                SubCommand::create_an_sub_command("build")
+                    .action(Arg::Bool(&|_x| {}))
+                    .add_example("app build true", "编译"),
+            )
+            .add_app_example("app -h", "查看帮助文档")
+            .add_app_example("app build false", "编译")
+            .add_app_example("app --list-all-commands", "列出所有子命令")
+            .add_app_example("app --dry-run build true", "只打印不执行")
+            .add_app_example("app --yes build true", "不需要确认");
+        assert!(app.debug_app_example_check().is_success());
+        assert_eq!(try_run_with(&app, &["app", "-e"]), DidHandled::Handled);
+
+        let app = app
+            .add_app_example("app build 3", "参数类型错误")
+            .add_app_example("app unknown", "未知命令")
+            .add_app_example("cmd -h", "程序名称错误");
+        let re = app.debug_app_example_check();
+        assert_eq!(re.failures_examples.len(), 3);
+        assert_eq!(re.success_examples.len(), 5);
+    }
+
+    #[test]
+    fn list_all_commands() {
+        let app = App::new()
+            .app_name("app")
+            .add_command(
+                SubCommand::create_an_sub_command("build")
+                    .short_name("b")
+                    .about("编译项目")
+                    .action(Arg::Bool(&|_x| {}))
+                    .add_example("app build true", "编译"),
+            )
+            .add_command(SubCommand::create_an_sub_command("compile").hidden(true));
+
+        assert_eq!(
+            try_run_with(&app, &["app", "--list-all-commands"]),
+            DidHandled::Handled
+        );
+        assert_eq!(
+            try_run_with(&app, &["app", "--list-all-commands", "toml"]),
+            DidHandled::Handled
+        );
+        assert!(try_run_with(&app, &["app", "--list-all-commands", "json"]) != DidHandled::Handled);
+
+        let toml_str = app.formated_all_commands_toml().unwrap();
+        assert!(toml_str.contains(r#"name = "build""#));
+        assert!(toml_str.contains(r#"short_name = "b""#));
+        assert!(toml_str.contains(r#"arg_type = "Bool""#));
+        assert!(toml_str.contains("examples = 1"));
+        assert!(!toml_str.contains("compile"));
+    }
+
+    #[test]
+    fn script_keep_going() {
+        let app = App::new()
+            .app_name("app")
+            .add_command(SubCommand::create_an_sub_command("build").action(Arg::Bool(&|_x| {})));
+        let script = r#"
+# 编译
+app build true
+build 3
+build false
+"#;
+
+        let re = app.run_script_content("runbook", script, true);
+        assert_eq!(re.results.len(), 3);
+        assert_eq!(re.failed_count(), 1);
+        assert_eq!(re.skipped, 0);
+        assert!(!re.is_success());
+
+        let re = app.run_script_content("runbook", script, false);
+        assert_eq!(re.results.len(), 2);
+        assert_eq!(re.skipped, 1);
+    }
+
+    #[test]
+    fn multi_call() {
+        let app = App::new().app_name("app").multi_call().add_command(
+            SubCommand::create_an_sub_command("build")
+                .short_name("b")
+                .action(Arg::Bool(&|_x| {})),
+        );
+
+        assert_eq!(
+            try_run_with(&app, &["/usr/bin/build", "true"]),
+            DidHandled::Handled
+        );
+        assert_eq!(try_run_with(&app, &["b", "-h"]), DidHandled::Handled);
+        assert!(try_run_with(&app, &["build", "build", "true"]) != DidHandled::Handled);
+        assert_eq!(
+            try_run_with(&app, &["app", "build", "true"]),
+            DidHandled::Handled
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn plugins_on_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("chenbao_cmd_plugins_on_path");
+        std::fs::create_dir_all(&dir).unwrap();
+        let plugin_path = dir.join("plugintest-hello");
+        std::fs::write(&plugin_path, "#!/bin/sh\nexit 0\n").unwrap();
+        std::fs::set_permissions(&plugin_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let failing_path = dir.join("plugintest-fail");
+        std::fs::write(&failing_path, "#!/bin/sh\nexit 3\n").unwrap();
+        std::fs::set_permissions(&failing_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let dirs = vec![dir.clone()];
+        assert_eq!(
+            plugin::find_plugin(&dirs, "plugintest", "hello"),
+            Some(plugin_path.clone())
+        );
+        assert_eq!(
+            plugin::find_plugin(&dirs, "plugintest", "../plugintest-hello"),
+            None
+        );
+
+        // 不修改 PATH, 其他测试会同时读取环境变量.
+        let mut app = App::new().app_name("plugintest");
+        app._plugin_dirs = dirs;
+        assert!(try_run_with(&app, &["plugintest", "hello"]) != DidHandled::Handled);
+        assert!(app.plugins().is_empty());
+
+        let app = app.enable_plugins();
+        assert_eq!(
+            try_run_with(&app, &["plugintest", "hello", "world"]),
+            DidHandled::Handled
+        );
+        assert!(app.plugins().iter().any(|x| x.name == "hello"));
+        assert!(app.formated_commands().contains("Plugins:"));
+        assert!(app.formated_all_commands().contains("Plugins:"));
+        assert!(app
+            .formated_all_commands_toml()
+            .unwrap()
+            .contains(r#"name = "hello""#));
+
+        // 真正执行外部子命令.
+        let mut app = app;
+        app._need_to = NeedTo::Run;
+        assert_eq!(
+            app.try_run_args(vec!["hello".to_string()]),
+            DidHandled::Handled
+        );
+
+        // 外部子命令的退出码不是 0 时, 返回这个退出码.
+        assert_eq!(app.try_run_args(vec!["fail".to_string()]).exit_code(), 3);
+    }
+
+    #[test]
+    fn mount_app() {
+        let tool = App::new()
+            .app_name("tool")
+            .about("一个内部工具")
+            .add_command(
+                SubCommand::create_an_sub_command("build")
+                    .short_name("b")
+                    .action(Arg::Bool(&|_x| {}))
+                    .add_example("tool build true", "编译项目"),
+            )
+            .add_app_example("tool -h", "查看帮助文档");
+
+        let app = App::new()
+            .app_name("umbrella")
+            .mount("tool", tool)
+            .add_command(
+                SubCommand::create_an_sub_command("run")
+                    .add_example("umbrella tool b false", "挂载的子命令"),
+            );
+
+        assert_eq!(
+            try_run_with(&app, &["umbrella", "tool", "build", "true"]),
+            DidHandled::Handled
+        );
+        assert_eq!(
+            try_run_with(&app, &["umbrella", "tool", "-h"]),
+            DidHandled::Handled
+        );
+        assert_eq!(
+            try_run_with(&app, &["umbrella", "tool", "-e"]),
+            DidHandled::Handled
+        );
+        assert!(try_run_with(&app, &["umbrella", "tool", "build", "3"]) != DidHandled::Handled);
+
+        let (prefix, mounted) = &app.mounted_apps()[0];
+        assert_eq!(prefix, "umbrella tool");
+        assert!(mounted.debug_app_example_check().is_success());
+        assert!(mounted.debug_example_check().iter().all(|x| x.is_success()));
+        assert!(!app.debug_example_messages().is_empty());
+
+        let toml_str = app.formated_all_commands_toml().unwrap();
+        assert!(toml_str.contains(r#"name = "tool build""#));
+        assert!(toml_str.contains(r#"short_name = "tool b""#));
+    }
+
+    #[test]
+    fn lifecycle_hooks() {
+        use std::sync::Mutex;
+
+        static CALLS: Mutex<Vec<String>> = Mutex::new(vec![]);
+        fn log(s: String) {
+            CALLS.lock().unwrap().push(s);
+        }
+
+        let app = App::new()
+            .app_name("app")
+            .before(&|name, args| {
+                log(format!("app before {} {:?}", name, args));
+                if args.first().map(|x| x.as_str()) == Some("false") {
+                    return Err("abort".to_string());
+                }
+                Ok(())
+            })
+            .after(&|name, _args| {
+                log(format!("app after {}", name));
+                Ok(())
+            })
+            .add_command(
+                SubCommand::create_an_sub_command("build")
+                    .before(&|name, _args| {
+                        log(format!("cmd before {}", name));
+                        Ok(())
+                    })
+                    .after(&|name, _args| {
+                        log(format!("cmd after {}", name));
+                        Ok(())
+                    })
+                    .action(Arg::Bool(&|_x| log("action".to_string()))),
+            );
+
+        let mut app = app;
+        app._need_to = NeedTo::Run;
+
+        assert_eq!(
+            app.try_run_args(vec!["build".to_string(), "true".to_string()]),
+            DidHandled::Handled
+        );
+        assert_eq!(
+            *CALLS.lock().unwrap(),
+            vec![
+                r#"app before build ["true"]"#,
+                "cmd before build",
+                "action",
+                "cmd after build",
+                "app after build",
+            ]
+        );
+
+        CALLS.lock().unwrap().clear();
+        assert_eq!(
+            app.try_run_args(vec!["build".to_string(), "false".to_string()]),
+            DidHandled::Failed("abort".to_string())
+        );
+        assert_eq!(
+            *CALLS.lock().unwrap(),
+            vec![r#"app before build ["false"]"#]
+        );
+
+        // -h 和只解析不执行时不会调用 hook.
+        CALLS.lock().unwrap().clear();
+        app.try_run_args(vec!["build".to_string(), "-h".to_string()]);
+        try_run_with(&app, &["app", "build", "true"]);
+        assert!(CALLS.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn dry_run() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        static CALLED: AtomicBool = AtomicBool::new(false);
+
+        let app = App::new()
+            .app_name("app")
+            .before(&|_name, _args| {
+                CALLED.store(true, Ordering::SeqCst);
+                Ok(())
+            })
+            .add_command(
+                SubCommand::create_an_sub_command("build").action(Arg::NumberMutiple(&|_x| {
+                    CALLED.store(true, Ordering::SeqCst)
+                })),
+            )
+            .add_command(
+                SubCommand::create_an_sub_command("dialog").action(Arg::Dialog(&|_r| {
+                    CALLED.store(true, Ordering::SeqCst);
+                })),
+            );
+
+        assert_eq!(
+            run_with(&app, &["--dry-run", "build", "1", "2"]),
+            DidHandled::Handled
+        );
+        assert_eq!(
+            run_with(&app, &["--dry-run", "dialog"]),
+            DidHandled::Handled
+        );
+        assert!(run_with(&app, &["--dry-run", "build", "x"]) != DidHandled::Handled);
+        assert!(run_with(&app, &["--dry-run"]) != DidHandled::Handled);
+        assert!(!CALLED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn dangerous_command() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLED: AtomicUsize = AtomicUsize::new(0);
+
+        let app = App::new().app_name("app").add_command(
+            SubCommand::create_an_sub_command("clean")
+                .dangerous(true)
+                .action(Arg::String(&|_x| {
+                    CALLED.fetch_add(1, Ordering::SeqCst);
+                })),
+        );
+
+        assert_eq!(
+            run_with(&app, &["--yes", "clean", "db"]),
+            DidHandled::Handled
+        );
+        assert_eq!(CALLED.load(Ordering::SeqCst), 1);
+
+        // Arg::Dialog 使用答案文件时, 先读取答案文件再确认.
+        static ANSWERED: AtomicUsize = AtomicUsize::new(0);
+
+        let app = App::new().app_name("app").add_command(
+            SubCommand::create_an_sub_command("drop")
+                .dangerous(true)
+                .action(Arg::Dialog(&|r| {
+                    r.key("name").string("名称").unwrap();
+                    ANSWERED.fetch_add(1, Ordering::SeqCst);
+                })),
+        );
+
+        let path = std::env::temp_dir().join("chenbao_cmd_dangerous_command.answers.toml");
+        std::fs::write(&path, "name = \"db\"").unwrap();
+        let path = path.display().to_string();
+
+        assert_eq!(
+            run_with(&app, &["--yes", "drop", &path]),
+            DidHandled::Handled
+        );
+        assert_eq!(ANSWERED.load(Ordering::SeqCst), 1);
+
+        assert_eq!(yes_env_var("my-app"), "MY_APP_YES");
+    }
+
+    #[test]
+    fn dangerous_command_without_tty() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        if std::env::var_os("CHENBAO_CMD_TEST_WITHOUT_TTY").is_none() {
+            // 在 stdin 不是终端的子进程中执行, 在终端中运行测试时也会检查.
+            let status = std::process::Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "application::test_app::dangerous_command_without_tty",
+                    "--exact",
+                    "--quiet",
+                ])
+                .env("CHENBAO_CMD_TEST_WITHOUT_TTY", "1")
+                .stdin(std::process::Stdio::null())
+                .status()
+                .unwrap();
+            assert!(status.success());
+            return;
         }
+
+        static CALLED: AtomicUsize = AtomicUsize::new(0);
+
+        let app = App::new().app_name("app").add_command(
+            SubCommand::create_an_sub_command("clean")
+                .dangerous(true)
+                .action(Arg::String(&|_x| {
+                    CALLED.fetch_add(1, Ordering::SeqCst);
+                })),
+        );
+
+        // 不是终端, 也没有 --yes, 执行失败.
+        assert!(run_with(&app, &["clean", "db"]) != DidHandled::Handled);
+        assert_eq!(CALLED.load(Ordering::SeqCst), 0);
+
+        // Arg::Dialog 读取答案文件之后, 确认失败, 不会执行 action.
+        static ANSWERED: AtomicUsize = AtomicUsize::new(0);
+
+        let app = App::new().app_name("app").add_command(
+            SubCommand::create_an_sub_command("drop")
+                .dangerous(true)
+                .action(Arg::Dialog(&|r| {
+                    r.key("name").string("名称").unwrap();
+                    ANSWERED.fetch_add(1, Ordering::SeqCst);
+                })),
+        );
+
+        let path =
+            std::env::temp_dir().join("chenbao_cmd_dangerous_command_without_tty.answers.toml");
+        std::fs::write(&path, "name = \"db\"").unwrap();
+        let path = path.display().to_string();
+
+        assert!(run_with(&app, &["drop", &path]) != DidHandled::Handled);
+        assert_eq!(ANSWERED.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn action_each() {
+        use crate::ItemAction;
+        use std::sync::atomic::{AtomicI64, Ordering};
+
+        static SUM: AtomicI64 = AtomicI64::new(0);
+
+        let app = App::new().app_name("app").add_command(
+            SubCommand::create_an_sub_command("add")
+                .action_each(ItemAction::Number(&|x| {
+                    SUM.fetch_add(x, Ordering::SeqCst);
+                    Ok(())
+                }))
+                .parallel(2),
+        );
+
+        assert!(run_with(&app, &["add", "1", "x"]) != DidHandled::Handled);
+
+        assert_eq!(run_with(&app, &["add", "1", "2", "3"]), DidHandled::Handled);
+        assert_eq!(
+            run_with(&app, &["--dry-run", "add", "100"]),
+            DidHandled::Handled
+        );
+        assert_eq!(SUM.load(Ordering::SeqCst), 6);
+
+        // 有参数执行失败时返回 Failed, 其他参数照常执行.
+        let app = App::new().app_name("app").add_command(
+            SubCommand::create_an_sub_command("check").action_each(ItemAction::Number(&|x| {
+                SUM.fetch_add(x, Ordering::SeqCst);
+                if x % 2 == 0 {
+                    Ok(())
+                } else {
+                    Err(format!("{x} 是奇数"))
+                }
+            })),
+        );
+
+        assert!(run_with(&app, &["check", "1", "10"]) != DidHandled::Handled);
+        assert_eq!(SUM.load(Ordering::SeqCst), 17);
+    }
+
+    #[test]
+    fn command_groups_order() {
+        let app = App::new()
+            .group_order(&["Project", "Maintenance"])
+            .add_command(SubCommand::create_an_sub_command("clean").group("Maintenance"))
+            .add_command(SubCommand::create_an_sub_command("run"))
+            .add_command(SubCommand::create_an_sub_command("init").group("Project"))
+            .add_command(SubCommand::create_an_sub_command("docs").group("Other"));
+
+        let help = app.formated_commands();
+        let position = |s: &str| help.find(s).unwrap();
+
+        assert!(position("Commands:") < position("Project:"));
+        assert!(position("Project:") < position("init"));
+        assert!(position("init") < position("Maintenance:"));
+        assert!(position("Maintenance:") < position("clean"));
+        assert!(position("clean") < position("Other:"));
     }
 }
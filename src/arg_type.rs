@@ -39,6 +39,12 @@ pub(crate) enum ReplArg {
     // PathMultiple(Vec<String>),
     Bool(bool),
     BoolMultiple(Vec<bool>),
+
+    /// `DialogGenerator::section()` 中的问题的答案.
+    Table(ReplArgStore),
+
+    /// `DialogGenerator::repeat()` 中每一次的答案.
+    Tables(Vec<ReplArgStore>),
 }
 
 impl ReplArg {
@@ -109,6 +115,19 @@ impl ReplArg {
         Err(format!("类型不正确: {:?}", self))
     }
 
+    /// 文字形式的值, 单个值时只有一个元素.
+    pub fn texts(&self) -> Vec<String> {
+        match self {
+            ReplArg::Number(x) => vec![x.to_string()],
+            ReplArg::NumberMultiple(x) => x.iter().map(|x| x.to_string()).collect(),
+            ReplArg::String(x) => vec![x.clone()],
+            ReplArg::StringMultiple(x) => x.clone(),
+            ReplArg::Bool(x) => vec![x.to_string()],
+            ReplArg::BoolMultiple(x) => x.iter().map(|x| x.to_string()).collect(),
+            ReplArg::Table(_) | ReplArg::Tables(_) => vec![],
+        }
+    }
+
     // pub fn get_bool_multiple(&self) -> &Vec<bool> {
     //     if let ReplArg::BoolMultiple(val) = self {
     //         return val;
@@ -129,10 +148,6 @@ impl ReplArgStore {
         toml::from_str(s)
     }
 
-    pub fn add(&mut self, index: usize, prompt: &str, v: ReplArg) -> Option<ReplArg> {
-        self.insert(key_gen(index, prompt), v)
-    }
-
     pub fn insert(&mut self, k: String, v: ReplArg) -> Option<ReplArg> {
         self.0.insert(k, v)
     }
@@ -147,11 +162,15 @@ impl ReplArgStore {
         self.0.get(&key)
     }
 
-    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
-        for (key, val) in &self.0 {
-            println!("key: {}, val: {:?}", key, val);
-        }
+    pub fn remove_by_key(&mut self, key: &str) -> Option<ReplArg> {
+        self.0.remove(key)
+    }
 
+    pub fn get_by_key(&self, key: &str) -> Option<&ReplArg> {
+        self.0.get(key)
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
         toml::to_string_pretty(&self)
     }
 }
@@ -196,7 +215,7 @@ fn toml_vec_numbers() {
     let mut index = 0_usize;
     val.iter().for_each(|x| {
         let key = "key".to_string();
-        toml_store.add(index, &key, x.clone());
+        toml_store.insert(key_gen(index, &key), x.clone());
         index += 1;
     });
 
@@ -0,0 +1,151 @@
+//! 多个参数时, 每个参数分别执行一次 action, 类似 `cargo add A B C` 相当于执行 3 次 `cargo add`.
+
+use crate::arg_type;
+use crate::helper::*;
+use owo_colors::OwoColorize;
+use prettytable::{row, table};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+/// 处理单个参数的 action, 返回 `Err` 表示这个参数处理失败.
+/// 需要实现 `Sync`, 以便并行执行.
+pub type ItemFn<T> = dyn Fn(T) -> Result<(), String> + Sync;
+
+/// 逐个参数执行的 action, 请使用 `SubCommand::action_each()` 设置.
+#[derive(Clone)]
+pub enum ItemAction {
+    String(&'static ItemFn<arg_type::String>),
+
+    Number(&'static ItemFn<arg_type::Number>),
+
+    Path(&'static ItemFn<arg_type::Path>),
+
+    Bool(&'static ItemFn<arg_type::Bool>),
+}
+
+impl std::fmt::Debug for ItemAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String(_arg0) => f.debug_tuple("String(_)").finish(),
+            Self::Number(_arg0) => f.debug_tuple("Number(_)").finish(),
+            Self::Path(_arg0) => f.debug_tuple("Path(_)").finish(),
+            Self::Bool(_arg0) => f.debug_tuple("Bool(_)").finish(),
+        }
+    }
+}
+
+/// 单个参数的执行结果.
+pub(crate) struct ItemResult {
+    pub item: String,
+    pub result: Result<(), String>,
+}
+
+/// 逐个执行 `f`, `workers` 大于 1 时最多同时执行 `workers` 个.
+/// 结果的顺序和 `items` 的顺序相同.
+pub(crate) fn run_items<T>(items: Vec<T>, f: &ItemFn<T>, workers: usize) -> Vec<ItemResult>
+where
+    T: std::fmt::Debug + Clone + Send + Sync,
+{
+    let run = |x: &T| ItemResult {
+        item: format!("{:?}", x),
+        result: f(x.clone()),
+    };
+
+    if workers <= 1 || items.len() <= 1 {
+        return items.iter().map(run).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<ItemResult>>> =
+        Mutex::new((0..items.len()).map(|_| None).collect());
+
+    std::thread::scope(|s| {
+        for _ in 0..workers.min(items.len()) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(x) = items.get(i) else {
+                    break;
+                };
+
+                let re = run(x);
+                if let Ok(mut results) = results.lock() {
+                    results[i] = Some(re);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// 所有参数的执行结果.
+pub(crate) struct EachResult {
+    pub cmd_name: String,
+    pub results: Vec<ItemResult>,
+}
+
+impl EachResult {
+    pub fn failed_count(&self) -> usize {
+        self.results.iter().filter(|x| x.result.is_err()).count()
+    }
+
+    /// 每个参数的执行结果.
+    pub fn formated_massage(&self) -> String {
+        let mut table = table!();
+        table.set_format(result_table_formater());
+
+        let ok = if self.failed_count() == 0 {
+            "ok".green().to_string()
+        } else {
+            "FAILED".red().to_string()
+        };
+
+        table.set_titles(row![format!(
+            "{} ... {ok}    {} passed; {} failed",
+            self.cmd_name.styled_sub_command(),
+            self.results.len() - self.failed_count(),
+            self.failed_count(),
+        )]);
+
+        for x in &self.results {
+            let state = match &x.result {
+                Ok(_) => "ok".green().to_string(),
+                Err(_) => "FAILED".red().to_string(),
+            };
+
+            table.add_row(row![format!("{state}  {}", x.item.styled_arg())]);
+
+            if let Err(err) = &x.result {
+                table.add_row(row![err.trim()]);
+            }
+        }
+
+        format!("\n{}", table)
+    }
+}
+
+#[test]
+fn test_run_items() {
+    let f: &ItemFn<i64> = &|x| {
+        if x % 2 == 0 {
+            Ok(())
+        } else {
+            Err(format!("{x} 是奇数"))
+        }
+    };
+
+    for workers in [1, 3] {
+        let re = run_items((0..10).collect(), f, workers);
+        assert_eq!(re.len(), 10);
+        assert_eq!(re[3].item, "3");
+        assert!(re[4].result.is_ok());
+        assert!(re[5].result.is_err());
+    }
+}
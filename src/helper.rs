@@ -1,5 +1,8 @@
 use owo_colors::OwoColorize;
-use prettytable::{format::TableFormat, table, Row, Table};
+use prettytable::{
+    format::{LinePosition, LineSeparator, TableFormat},
+    table, Row, Table,
+};
 
 // pub(crate) fn is_debug_mode() -> bool {
 //     return cfg!(debug_assertions);
@@ -47,6 +50,35 @@ pub(crate) fn parse_arg_string(input: &str) -> Vec<String> {
     result
 }
 
+/// 移除 `args` 开头的程序名称.
+/// `app_name` 可以包含空格, 例如挂载到其他 App 下面时的 `"umbrella tool"`.
+/// 程序名称不正确时返回实际收到的程序名称.
+pub(crate) fn remove_app_name(args: &mut Vec<String>, app_name: &str) -> Result<(), String> {
+    let expected = parse_arg_string(app_name);
+    let n = expected.len().max(1).min(args.len());
+
+    let received: Vec<String> = args.drain(..n).collect();
+    if received == expected {
+        Ok(())
+    } else {
+        Err(received.join(" "))
+    }
+}
+
+#[test]
+fn test_remove_app_name() {
+    let mut args = parse_arg_string("umbrella tool build true");
+    assert_eq!(remove_app_name(&mut args, "umbrella tool"), Ok(()));
+    assert_eq!(args, vec!["build", "true"]);
+
+    let mut args = parse_arg_string("app build true");
+    assert_eq!(
+        remove_app_name(&mut args, "umbrella tool"),
+        Err("app build".to_string())
+    );
+    assert_eq!(args, vec!["true"]);
+}
+
 #[test]
 fn test_parse_string() {
     let input = r#" "a" "b" c "d" e 32424 "32543" "a b dsaf" asdfsaf 767544  "a b c\" d e""#;
@@ -75,6 +107,20 @@ pub(crate) fn plain_table_formater() -> TableFormat {
     f
 }
 
+/// 执行结果的表格, 标题是执行结果, 左边有边框. 示例的测试结果, `App::run_script()` 和 `action_each()` 的结果使用.
+pub(crate) fn result_table_formater() -> TableFormat {
+    let mut f = TableFormat::new();
+    {
+        f.padding(2, 0);
+        f.column_separator(' ');
+        f.separator(LinePosition::Bottom, LineSeparator::new('─', '─', '└', 'r'));
+        f.separator(LinePosition::Title, LineSeparator::new('━', '━', '┝', 'r'));
+        f.left_border('│');
+    }
+
+    f
+}
+
 pub(crate) trait StyledString {
     fn styled_sub_command(&self) -> String;
     fn styled_arg_type(&self) -> String;
@@ -251,3 +297,35 @@ pub fn vec_row_to_table(arr: Vec<Row>) -> Table {
 
     table
 }
+
+/// 最外层 App 的名称, 挂载的 App 的名称是 `"app sub"`, 返回 `"app"`.
+pub(crate) fn root_app_name(app_name: &str) -> &str {
+    app_name.split(' ').next().unwrap_or_default()
+}
+
+/// 跳过危险子命令确认的环境变量的名称, 例如 `my-app` 对应 `MY_APP_YES`.
+pub(crate) fn yes_env_var(app_name: &str) -> String {
+    app_env_var(app_name, "YES")
+}
+
+/// `Arg::Dialog` 子命令的答案文件的路径的环境变量的名称, 每个子命令一个,
+/// 例如 `my-app` 的 `new` 对应 `MY_APP_NEW_ANSWERS`.
+/// * `app_name`: 挂载的 App 中是 `"app sub"`.
+pub(crate) fn answers_env_var(app_name: &str, cmd_name: &str) -> String {
+    app_env_var(&format!("{} {}", app_name, cmd_name), "ANSWERS")
+}
+
+fn app_env_var(app_name: &str, suffix: &str) -> String {
+    let name: String = app_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("{}_{}", name, suffix)
+}
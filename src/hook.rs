@@ -0,0 +1,126 @@
+//! 执行子命令的 action 之前和之后的 hook, 以及包裹子命令执行过程的 middleware.
+
+use crate::DidHandled;
+use core::fmt;
+
+/// hook 的类型.
+/// 参数是子命令的名称和子命令接收到的参数, 返回 `Err` 时停止执行并报错.
+pub type HookFn = dyn Fn(&str, &[String]) -> Result<(), String>;
+
+/// `App::before()` `App::after()` `SubCommand::before()` `SubCommand::after()` 设置的 hook.
+#[derive(Clone, Copy)]
+pub(crate) struct Hook(pub &'static HookFn);
+
+impl fmt::Debug for Hook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Hook(_)").finish()
+    }
+}
+
+/// 依次执行 hooks, 遇到 `Err` 时立即返回.
+pub(crate) fn run_hooks(hooks: &[Hook], cmd_name: &str, cmd_args: &[String]) -> Result<(), String> {
+    for x in hooks {
+        (x.0)(cmd_name, cmd_args)?;
+    }
+
+    Ok(())
+}
+
+/// 是否是每个子命令都有的 flag: -h --help -e --example.
+/// 这些 flag 不会触发 hook.
+pub(crate) fn is_command_flag(first_arg: Option<&String>) -> bool {
+    match first_arg {
+        Some(x) => ["-h", "--help", "-e", "--example"].contains(&x.as_str()),
+        None => false,
+    }
+}
+
+// ------- Middleware -------
+
+/// 一次子命令的执行, middleware 可以通过它知道正在执行哪个子命令.
+#[derive(Clone, Debug)]
+pub struct Invocation<'a> {
+    /// 程序名称, 挂载的 App 中是 `"umbrella tool"` 这样的形式.
+    pub app_name: &'a str,
+
+    /// 子命令的名称.
+    pub cmd_name: &'a str,
+
+    /// 子命令接收到的参数.
+    pub args: &'a [String],
+}
+
+/// middleware 的类型.
+/// 第二个参数 `next` 执行下一层 middleware, 最里面一层是子命令本身,
+/// 可以不调用 (例如权限检查失败), 也可以调用多次 (例如重试).
+pub type MiddlewareFn = dyn Fn(&Invocation, &dyn Fn() -> DidHandled) -> DidHandled;
+
+/// `App::middleware()` 设置的 middleware.
+#[derive(Clone, Copy)]
+pub(crate) struct Middleware(pub &'static MiddlewareFn);
+
+impl fmt::Debug for Middleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Middleware(_)").finish()
+    }
+}
+
+/// 依次执行 middlewares, 第一个在最外层, `last` 在最里层.
+pub(crate) fn run_middlewares(
+    middlewares: &[Middleware],
+    invocation: &Invocation,
+    last: &dyn Fn() -> DidHandled,
+) -> DidHandled {
+    match middlewares.split_first() {
+        None => last(),
+        Some((first, rest)) => (first.0)(invocation, &|| run_middlewares(rest, invocation, last)),
+    }
+}
+
+#[test]
+fn test_run_middlewares() {
+    use std::cell::Cell;
+
+    let invocation = Invocation {
+        app_name: "app",
+        cmd_name: "build",
+        args: &[],
+    };
+
+    // 重试, 直到成功.
+    let retry = Middleware(&|_inv, next| {
+        let mut re = next();
+        for _ in 0..3 {
+            if re == DidHandled::Handled {
+                break;
+            }
+            re = next();
+        }
+        re
+    });
+
+    // 在失败信息前面加上子命令的名称.
+    let prefix = Middleware(&|inv, next| next().map_err(|e| format!("{}: {}", inv.cmd_name, e)));
+
+    let count = Cell::new(0);
+    let last = || {
+        count.set(count.get() + 1);
+        if count.get() < 3 {
+            DidHandled::Failed("err".to_string())
+        } else {
+            DidHandled::Handled
+        }
+    };
+
+    assert_eq!(
+        run_middlewares(&[prefix, retry], &invocation, &last),
+        DidHandled::Handled
+    );
+    assert_eq!(count.get(), 3);
+
+    count.set(-10);
+    assert_eq!(
+        run_middlewares(&[prefix, retry], &invocation, &last),
+        DidHandled::Failed("build: err".to_string())
+    );
+}
@@ -66,6 +66,9 @@
 //!
 //! app -v                      # 查看 app 的版本信息
 //! app --version               # 查看 app 的版本信息
+//!
+//! app -e                      # 查看示例
+//! app --example               # 查看示例
 //! ```
 //!
 //!
@@ -90,17 +93,27 @@
 pub use action::Arg;
 pub use application::App;
 pub use application::DidHandled;
-pub use chenbao_cmd_macro::cmd;
+pub use chenbao_cmd_macro::{cmd, FromDialog};
+pub use each::{ItemAction, ItemFn};
+pub use hook::{HookFn, Invocation, MiddlewareFn};
+pub use question_and_anser::FromDialog;
 pub mod arg_type;
 pub use subcommand::SubCommand;
+pub use validator::{Validator, ValidatorFn};
 
 // ------- Private -------
 
 mod action;
 mod application;
+mod each;
 mod examples_types;
 mod helper;
+mod hook;
+mod plugin;
 mod question_and_anser;
+mod script;
+mod shell;
 mod subcommand;
+mod validator;
 
 pub(crate) type SharedVecString = std::rc::Rc<Vec<String>>;
@@ -0,0 +1,93 @@
+//! 在 PATH 中查找 `<app_name>-<command>` 形式的外部子命令, 类似 `git foo` 执行 `git-foo`.
+
+use std::path::{Path, PathBuf};
+
+/// PATH 中的一个外部子命令.
+#[derive(serde::Serialize, Clone, Debug)]
+pub(crate) struct Plugin {
+    /// 子命令的名称, 也就是去掉 `<app_name>-` 前缀之后的文件名.
+    pub name: String,
+
+    /// 可执行文件的路径.
+    pub path: PathBuf,
+}
+
+/// 在 `dirs` 中查找 `<app_name>-<command>` 可执行文件.
+/// `command` 中有路径分隔符时返回 `None`, 避免 `app ../x` 执行其他目录中的文件.
+pub(crate) fn find_plugin(dirs: &[PathBuf], app_name: &str, command: &str) -> Option<PathBuf> {
+    if app_name.is_empty()
+        || command.is_empty()
+        || command.starts_with('-')
+        || command.contains(['/', '\\'])
+    {
+        return None;
+    }
+
+    let file_name = format!("{}-{}", app_name, command);
+
+    dirs.iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|path| is_executable(path))
+}
+
+/// `dirs` 中所有的 `<app_name>-*` 可执行文件.
+/// 同名的只保留 `dirs` 中靠前的那个, 结果按名称排序.
+pub(crate) fn discover_plugins(dirs: &[PathBuf], app_name: &str) -> Vec<Plugin> {
+    if app_name.is_empty() {
+        return vec![];
+    }
+
+    let prefix = format!("{}-", app_name);
+    let mut re: Vec<Plugin> = vec![];
+
+    for dir in dirs {
+        let read_dir = match std::fs::read_dir(dir) {
+            Ok(x) => x,
+            Err(_e) => continue,
+        };
+
+        for entry in read_dir.filter_map(|x| x.ok()) {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let name = match file_name.strip_prefix(&prefix) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            if name.is_empty() || re.iter().any(|x| x.name == name) {
+                continue;
+            }
+
+            let path = entry.path();
+            if is_executable(&path) {
+                re.push(Plugin {
+                    name: name.to_string(),
+                    path,
+                });
+            }
+        }
+    }
+
+    re.sort_by(|a, b| a.name.cmp(&b.name));
+    re
+}
+
+/// PATH 中的目录.
+pub(crate) fn path_dirs() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|x| std::env::split_paths(&x).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .map(|x| x.is_file() && x.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
@@ -2,13 +2,22 @@ use crate::arg_type::ReplArgStore;
 
 use crate::helper::*;
 use owo_colors::OwoColorize;
-use std::{num::ParseIntError, path::Path, vec};
+use std::{cell::Cell, num::ParseIntError, path::Path, vec};
 
 use super::*;
 use arg_type::key_gen;
+use validator::{CompiledValidator, Validator};
 
 const ARGUMENTS_START_INDEX: usize = 1;
 
+/// `review()` 中输入这个文字返回上一个问题.
+const GO_BACK: &str = ":back";
+
+thread_local! {
+    /// 问答中的错误已经保存在 `DialogGenerator` 中时, 不打印 `unwrap()` 引起的 panic 信息.
+    static QUIET_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
 /// ArgType::Repl(_) 需要用到 ReplQuestions.  
 pub struct DialogGenerator {
     /// 从 json_str 转换过来的 Vec<String>.
@@ -22,6 +31,97 @@ pub struct DialogGenerator {
     /// 是否是从 json_str 转换过来的?
     pub is_from_toml: bool,
     theme: dialoguer::theme::ColorfulTheme,
+
+    /// 从 toml 读取答案时, 是否通过问答式交互补充了 toml 中没有的答案.
+    pub(crate) has_new_answers: bool,
+
+    /// 下一个问题的选项, 通过 `key()` `default_answer()` 等设置, 回答之后清空.
+    options: QuestionOptions,
+
+    /// 是否在 `review()` 中, 此时可以输入 `:back` 返回上一个问题.
+    reviewing: bool,
+
+    /// 是否输入了 `:back`, `review()` 会返回上一个问题.
+    going_back: bool,
+
+    /// 已经回答的问题的 key, 按回答的顺序排列, `review()` 使用.
+    answered: Vec<String>,
+
+    /// stdin 和 stderr 是否都是终端, 创建时检查一次.
+    /// 测试中设置为 `false`, 不管是否在终端中运行, 都按不是终端的情况处理.
+    interactive: bool,
+
+    /// stdin 不是终端时, 按问题的顺序从这里每行读取一个答案.
+    lines: Option<Box<dyn std::io::BufRead>>,
+
+    /// 从 stdin 读取答案时缺少答案, 提示如何使用答案文件.
+    replay_hint: String,
+
+    /// 从 stdin 读取答案时已经提问的问题, 缺少答案时列出.
+    line_questions: Vec<String>,
+
+    /// 问答中的第一个错误, 例如缺少答案或者 toml 中的答案不正确.
+    /// action 中通常使用 `unwrap()`, 所以由 `run_action()` 返回这个错误.
+    error: Option<String>,
+}
+
+/// 问题的选项.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct QuestionOptions {
+    /// 答案在 toml 中的 key, 没有设置时使用 `key_gen(index, prompt)`.
+    pub key: Option<String>,
+
+    /// 文字形式的默认答案.
+    pub default: Option<String>,
+
+    /// 输入框中的初始文字.
+    pub initial_text: Option<String>,
+
+    /// 显示在问题下面的帮助信息.
+    pub help: Option<String>,
+
+    /// 是否可以不填.
+    pub allow_empty: bool,
+
+    /// 答案的校验.
+    pub validators: Vec<CompiledValidator>,
+
+    /// `validate()` 设置的 validator 本身不正确, 例如 `Validator::Regex` 的正则表达式.
+    /// 回答这个问题时返回这个错误, 否则这个问题永远无法回答.
+    pub invalid_validator: Option<String>,
+
+    /// 是否可以输入 `:back` 或者按 Esc 返回上一个问题.
+    pub can_go_back: bool,
+}
+
+impl QuestionOptions {
+    /// 问题和帮助信息.
+    fn formated_prompt(&self, prompt: &str) -> String {
+        match &self.help {
+            Some(help) => format!("{}\n  {}", prompt, help.dimmed()),
+            None => prompt.to_string(),
+        }
+    }
+}
+
+/// 从 `Arg::Dialog` 的问答中获取一个结构体, 通常使用 `#[derive(FromDialog)]` 实现.
+/// ```
+/// use chenbao_cmd::*;
+///
+/// #[derive(FromDialog)]
+/// struct Deploy {
+///     #[dialog(prompt = "部署到哪个环境?", choices("dev", "prod"))]
+///     env: String,
+///     #[dialog(prompt = "实例数量")]
+///     count: i64,
+/// }
+///
+/// cmd!("deploy").action(Arg::Dialog(&|r| {
+///     let _deploy = Deploy::from_dialog(r).unwrap();
+/// }));
+/// ```
+pub trait FromDialog: Sized {
+    fn from_dialog(r: &mut DialogGenerator) -> Result<Self, String>;
 }
 
 impl Default for DialogGenerator {
@@ -48,10 +148,31 @@ impl DialogGenerator {
 
             is_from_toml: false,
             theme: dialoguer::theme::ColorfulTheme::default(),
+            has_new_answers: false,
+            options: QuestionOptions::default(),
+            reviewing: false,
+            going_back: false,
+            answered: vec![],
+            interactive: is_interactive(),
+            lines: None,
+            replay_hint: String::new(),
+            line_questions: vec![],
+            error: None,
             // 占位符号: PhantomData,
         }
     }
 
+    /// stdin 不是终端时使用, 按问题的顺序从 `lines` (通常是 stdin) 每行读取一个答案.
+    /// 空行表示使用默认答案, 多个值时用空格分开.
+    /// * `replay_hint`: 缺少答案时提示如何使用答案文件.
+    pub(crate) fn new_from_lines(lines: Box<dyn std::io::BufRead>, replay_hint: String) -> Self {
+        Self {
+            lines: Some(lines),
+            replay_hint,
+            ..Self::new()
+        }
+    }
+
     /// ```rs
     /// let cmd = crate::DialogGenerator::new_from_jsonstr(r#"["hello"]"#);
     /// ```
@@ -63,6 +184,16 @@ impl DialogGenerator {
 
                 is_from_toml: true,
                 theme: dialoguer::theme::ColorfulTheme::default(),
+                has_new_answers: false,
+                options: QuestionOptions::default(),
+                reviewing: false,
+                going_back: false,
+                answered: vec![],
+                interactive: is_interactive(),
+                lines: None,
+                replay_hint: String::new(),
+                line_questions: vec![],
+                error: None,
                 // 占位符号: PhantomData,
             })
             .map_err(|_e| format!("{}{}转换为 json 时出错: {}", file!(), line!(), _e))
@@ -80,189 +211,465 @@ impl DialogGenerator {
 }
 
 impl DialogGenerator {
-    // _string
+    /// 设置下一个问题在 toml 中的 key.
+    /// 没有设置 key 时使用 `"{序号}_{问题}"`, 增加问题或者修改问题的文字后, 之前保存的 toml 就无法使用了.
+    /// 设置了 key 之后, 只要 key 不变, 之前保存的 toml 就可以继续使用.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("deploy").action(Arg::Dialog(&|r| {
+    ///         let _env = r.key("env").string("部署到哪个环境?").unwrap();
+    ///     }));
+    /// ```
+    pub fn key(&mut self, key: &str) -> &mut Self {
+        self.options.key = Some(key.to_owned());
+        self
+    }
 
-    pub fn string(&mut self, prompt: &str) -> Result<arg_type::String, String> {
-        // pub fn string(&mut self, prompt: &str) -> &str {
-        if !self.is_from_toml {
-            let result_value = DialogerWraper::get_string(prompt, &self.theme);
+    /// 设置下一个问题的默认答案, 直接按回车键时使用默认答案.
+    /// 默认答案和其他答案一样会保存到 toml 中.
+    /// 从 toml 读取答案并且 toml 中没有这个问题的答案时, 如果 stdin 不是终端, 使用默认答案.
+    /// * 多个值时用空格分开, 例如 `"a b c"`.
+    /// * `yes_or_no` 的默认答案是 `true` 或者 `false`.
+    /// * `select` `select_multiple` 的默认答案是选项的文字.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("serve").action(Arg::Dialog(&|r| {
+    ///         let _port = r
+    ///             .key("port")
+    ///             .default_answer(8080)
+    ///             .help("1024 以下的端口需要 root 权限")
+    ///             .number("端口")
+    ///             .unwrap();
+    ///     }));
+    /// ```
+    pub fn default_answer(&mut self, value: impl ToString) -> &mut Self {
+        self.options.default = Some(value.to_string());
+        self
+    }
+
+    /// 设置下一个问题输入框中的初始文字, 用户可以在此基础上修改.
+    /// `editor()` 中是编辑器中的初始文字.
+    pub fn initial_text(&mut self, text: &str) -> &mut Self {
+        self.options.initial_text = Some(text.to_owned());
+        self
+    }
+
+    /// 设置下一个问题的帮助信息, 显示在问题的下面.
+    pub fn help(&mut self, help: &str) -> &mut Self {
+        self.options.help = Some(help.to_owned());
+        self
+    }
+
+    /// 下一个问题是否可以不填, 直接按回车键. 默认不可以.
+    pub fn allow_empty(&mut self, allow_empty: bool) -> &mut Self {
+        self.options.allow_empty = allow_empty;
+        self
+    }
 
-            self.arguments
-                .add(self.index, prompt, arg_type::ReplArg::String(result_value));
+    /// 为下一个问题添加答案的校验, 可以多次调用.
+    /// 交互式问答时校验失败会重新提问, 从 toml 读取答案时校验失败会返回错误.
+    /// `Validator::Regex` 的正则表达式不正确时, 这个问题返回错误, `Arg::Dialog` 执行失败.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("tag").action(Arg::Dialog(&|r| {
+    ///         let _tags = r
+    ///             .validate(Validator::Count(1, 3))
+    ///             .validate(Validator::Regex("^v[0-9.]+$"))
+    ///             .string_multiple("版本号")
+    ///             .unwrap();
+    ///     }));
+    /// ```
+    pub fn validate(&mut self, validator: Validator) -> &mut Self {
+        match validator.compile() {
+            Ok(validator) => self.options.validators.push(validator),
+            Err(err) => {
+                if self.options.invalid_validator.is_none() {
+                    self.options.invalid_validator = Some(err);
+                }
+            }
         }
 
-        let result_value = self.arguments.get(self.index, prompt).unwrap().get_string();
+        self
+    }
 
-        self.ret(result_value)
+    /// 当前问题在 toml 中的 key.
+    fn current_key(&self, prompt: &str) -> String {
+        match &self.options.key {
+            Some(key) => key.clone(),
+            None => key_gen(self.index, prompt),
+        }
     }
 
-    // _string_multiple
+    /// 保存当前问题的答案.
+    fn add(&mut self, prompt: &str, v: arg_type::ReplArg) {
+        let key = self.current_key(prompt);
+        self.answered.retain(|x| *x != key);
+        self.answered.push(key.clone());
+        self.arguments.insert(key, v);
+    }
+
+    /// 读取当前问题的答案.
+    /// 设置了 key 但是 toml 中没有这个 key 时, 使用 `"{序号}_{问题}"` 读取, 兼容设置 key 之前保存的 toml.
+    fn get(&self, prompt: &str) -> Option<&arg_type::ReplArg> {
+        self.options
+            .key
+            .as_ref()
+            .and_then(|key| self.arguments.get_by_key(key))
+            .or_else(|| self.arguments.get(self.index, prompt))
+    }
+
+    /// 获取当前问题的答案.
+    /// 1. 从 toml 读取答案时, 使用 toml 中的答案.
+    /// 2. toml 中没有这个问题的答案时, 如果 stdin 是终端则通过问答式交互获取,
+    ///    否则使用默认答案, 没有默认答案时返回错误.
+    /// * `ask`: 通过问答式交互获取答案, 返回答案和需要保存到 toml 中的值, 返回 `None` 表示返回上一个问题,
+    ///   无法使用问答式交互时返回错误.
+    /// * `from_toml`: 把 toml 中的值转换为答案.
+    /// * `parse`: 把文字形式的答案 (例如默认答案) 转换为答案和需要保存到 toml 中的值.
+    fn answer<T>(
+        &mut self,
+        prompt: &str,
+        ask: impl Fn(
+            &dialoguer::theme::ColorfulTheme,
+            &QuestionOptions,
+        ) -> Result<Option<(T, arg_type::ReplArg)>, String>,
+        from_toml: impl FnOnce(&arg_type::ReplArg) -> Result<T, String>,
+        parse: impl Fn(&str) -> Result<(T, arg_type::ReplArg), String>,
+    ) -> Result<T, String> {
+        if let Some(err) = self.options.invalid_validator.take() {
+            let err = format!("{}: {}", self.current_key(prompt).styled_arg(), err);
+            let re = self.record(Err(err));
+            return self.ret(re);
+        }
 
-    pub fn string_multiple(&mut self, prompt: &str) -> Result<arg_type::StringMutiple, String> {
         if self.is_from_toml {
-            let result_value = self
-                .arguments
-                .get(self.index, prompt)
-                .unwrap_or_else(|| panic!("没找到需要的参数: {}", key_gen(self.index, prompt)))
-                .get_string_multiple();
+            if let Some(val) = self.get(prompt).cloned() {
+                let result_value = self.check(prompt, &val).and_then(|_| {
+                    from_toml(&val).map_err(|err| format!("{}: {}", self.current_key(prompt), err))
+                });
+
+                // 使用 `"{序号}_{问题}"` 读取到的答案, 改为保存在设置的 key 中.
+                self.arguments.remove_by_key(&key_gen(self.index, prompt));
+                self.add(prompt, val);
+                let result_value = self.record(result_value);
+                return self.ret(result_value);
+            }
 
-            self.ret(result_value.map(|x| x.to_vec()))
-        } else {
-            let result_value = DialogerWraper::get_string_multiple(prompt, &self.theme);
+            if !self.is_interactive() && self.lines.is_none() {
+                let re = match &self.options.default {
+                    Some(default) => parse(default),
+                    None => Err(missing_answer(&self.current_key(prompt), prompt)),
+                };
+
+                let re = re.and_then(|(result_value, arg)| {
+                    self.check(prompt, &arg)?;
+                    self.add(prompt, arg);
+                    Ok(result_value)
+                });
+                let re = self.record(re);
+                return self.ret(re);
+            }
 
-            self.arguments.add(
-                self.index,
-                prompt,
-                arg_type::ReplArg::StringMultiple(result_value.clone()),
-            );
-            self.ret(Ok(result_value))
+            // toml 中没有这个问题的答案, 启动问答式交互.
+            self.has_new_answers = true;
+        }
+
+        self.options.can_go_back = self.reviewing;
+        if self.lines.is_some() {
+            let re = self
+                .line_answer(prompt, parse)
+                .and_then(|(result_value, arg)| {
+                    self.check(prompt, &arg)?;
+                    self.add(prompt, arg);
+                    Ok(result_value)
+                });
+            let re = self.record(re);
+            return self.ret(re);
+        }
+
+        loop {
+            let (result_value, arg) = match ask(&self.theme, &self.options) {
+                Ok(Some(x)) => x,
+                Ok(None) => {
+                    self.options = QuestionOptions::default();
+                    return Err(self.go_back());
+                }
+                Err(err) => {
+                    let re = self.record(Err(err));
+                    return self.ret(re);
+                }
+            };
+
+            match self.check(prompt, &arg) {
+                Ok(_) => {
+                    self.add(prompt, arg);
+                    return self.ret(Ok(result_value));
+                }
+                Err(err) => eprintln!("{}", err), // 继续本次问题
+            }
         }
     }
 
-    // _number
-    pub fn number(&mut self, prompt: &str) -> Result<i64, String> {
-        // let mut self = self;
+    /// 从 stdin 读取一行作为当前问题的答案, 空行时使用默认答案.
+    fn line_answer<T>(
+        &mut self,
+        prompt: &str,
+        parse: impl Fn(&str) -> Result<(T, arg_type::ReplArg), String>,
+    ) -> Result<(T, arg_type::ReplArg), String> {
+        let key = self.current_key(prompt);
+        let line = self.next_line(&key, prompt)?;
+        if self.options.can_go_back && line.trim() == GO_BACK {
+            return Err(self.go_back());
+        }
 
-        if self.is_from_toml {
-            let result_value = self
-                .arguments
-                .get(self.index, prompt)
-                .unwrap_or_else(|| panic!("key: {}", key_gen(self.index, prompt)))
-                .get_number();
+        match (&self.options.default, line.trim().is_empty()) {
+            (Some(default), true) => parse(default),
+            (None, true) if !self.options.allow_empty => Err(format!(
+                "{}: {} 不能为空",
+                "答案不正确".styled_error_marker(),
+                key.styled_arg()
+            )),
+            _ => parse(&line),
+        }
+    }
 
-            self.ret(result_value)
-        } else {
-            // get value from REPL.
+    /// 从 stdin 读取下一行, 没有更多的行时返回错误, 列出已经提问的问题和如何使用答案文件.
+    fn next_line(&mut self, key: &str, prompt: &str) -> Result<String, String> {
+        self.line_questions.push(format!("{} ({})", key, prompt));
 
-            let result_value = DialogerWraper::get_number(prompt, &self.theme);
+        match self.read_line() {
+            None => {
+                let questions: Vec<String> = self
+                    .line_questions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| format!("  {}. {}", i + 1, x))
+                    .collect();
 
-            self.arguments
-                .add(self.index, prompt, arg_type::ReplArg::Number(result_value));
+                Err(format!(
+                    "{}\nstdin 不是终端, 按问题的顺序每行读取一个答案:\n{}  <- 缺少答案\n{}",
+                    missing_answer(key, prompt),
+                    questions.join("\n"),
+                    self.replay_hint
+                ))
+            }
+            Some(line) => Ok(line),
+        }
+    }
 
-            self.ret(Ok(result_value))
+    /// 从 `lines` 读取下一行, 没有更多的行时返回 `None`.
+    fn read_line(&mut self) -> Option<String> {
+        let mut line = String::new();
+        match self.lines.as_mut()?.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_string()),
         }
     }
-    // _number_multiple
 
-    pub fn number_multiple(&mut self, prompt: &str) -> Result<arg_type::NumberMutiple, String> {
-        if self.is_from_toml {
-            let result_value = self
-                .arguments
-                .get(self.index, prompt)
-                // .expect(&format!("{:?}", key_gen(self.index, prompt)))
-                .unwrap_or_else(|| panic!("{:?}", key_gen(self.index, prompt)))
-                .get_number_multiple();
+    /// 输入了 `:back`, 返回的错误会让 `f` 停止, 然后 `review()` 返回上一个问题.
+    /// 即使 `f` 修改了错误信息或者使用了 `unwrap()`, `review()` 也能通过 `going_back` 知道需要返回上一个问题.
+    fn go_back(&mut self) -> String {
+        self.going_back = true;
+        QUIET_PANIC.with(|x| x.set(true));
+        format!("{} 返回上一个问题", GO_BACK)
+    }
 
-            self.ret(result_value.map(|x| x.to_vec()))
-        } else {
-            let multiple_string = DialogerWraper::get_string_multiple(prompt, &self.theme);
+    /// 是否可以使用问答式交互.
+    fn is_interactive(&self) -> bool {
+        self.lines.is_none() && self.interactive
+    }
 
-            let mut result_value: Vec<arg_type::Number> = vec![];
-            {
-                /* 为 result_value 赋值. */
+    /// 保存问答中的第一个错误, 见 `run_action()`.
+    fn record<T>(&mut self, re: Result<T, String>) -> Result<T, String> {
+        if let Err(err) = &re {
+            if !self.going_back && self.error.is_none() {
+                self.error = Some(err.clone());
+                QUIET_PANIC.with(|x| x.set(true));
+            }
+        }
+        re
+    }
 
-                for str in &multiple_string {
-                    let number_from_str: Result<arg_type::Number, std::num::ParseIntError> =
-                        str.parse();
+    /// 使用当前问题的所有 validator 校验答案.
+    fn check(&self, prompt: &str, arg: &arg_type::ReplArg) -> Result<(), String> {
+        let values = arg.texts();
+
+        self.options.validators.iter().try_for_each(|x| {
+            x.validate(&values).map_err(|err| {
+                format!(
+                    "{}: {} {}",
+                    "答案不正确".styled_error_marker(),
+                    self.current_key(prompt).styled_arg(),
+                    err
+                )
+            })
+        })
+    }
 
-                    if let Ok(x) = number_from_str {
-                        // 成功获取到了需要的参数
-                        result_value.push(x);
-                    } else {
-                        eprintln!("需要的是多个 bool 类型的值, 示例: true false true");
+    /// 通过输入框获取文字形式的答案, 无法转换时重新提问.
+    /// 可以返回上一个问题并且输入了 `:back` 时返回 `None`.
+    fn ask_text<T>(
+        prompt: &str,
+        theme: &dialoguer::theme::ColorfulTheme,
+        options: &QuestionOptions,
+        parse: impl Fn(&str) -> Result<(T, arg_type::ReplArg), String>,
+    ) -> Result<Option<(T, arg_type::ReplArg)>, String> {
+        loop {
+            let input = DialogerWraper::input(prompt, theme, options)?;
+            if options.can_go_back && input.trim() == GO_BACK {
+                return Ok(None);
+            }
 
-                        panic! {"{:?}", multiple_string};
-                    }
-                }
+            match parse(&input) {
+                Ok(x) => return Ok(Some(x)),
+                Err(err) => eprintln!("{}", err), // 继续本次问题
             }
-            self.arguments.add(
-                self.index,
-                prompt,
-                arg_type::ReplArg::NumberMultiple(result_value.clone()),
-            );
-            // self.ret(result_value)
-            self.ret(Ok(result_value))
         }
     }
-    // _yes_or_no
-    pub fn yes_or_no(&mut self, prompt: &str) -> Result<arg_type::Bool, String> {
-        if self.is_from_toml {
-            let result_value = self.arguments.get(self.index, prompt).unwrap().get_bool();
-            self.ret(result_value)
-        } else {
-            // get value from REPL.
 
-            let result_value = DialogerWraper::get_bool(prompt, &self.theme);
+    // _string
 
-            self.arguments
-                .add(self.index, prompt, arg_type::ReplArg::Bool(result_value)); // -> "true" or "false"
-            self.ret(Ok(result_value))
-        }
+    pub fn string(&mut self, prompt: &str) -> Result<arg_type::String, String> {
+        let parse = |x: &str| Ok((x.to_string(), arg_type::ReplArg::String(x.to_string())));
+
+        self.answer(
+            prompt,
+            |theme, options| Self::ask_text(prompt, theme, options, parse),
+            |x| x.get_string(),
+            parse,
+        )
     }
 
-    // _path
-    pub fn path(&mut self, prompt: &str) -> Result<arg_type::Path, String> {
-        if self.is_from_toml {
-            let val = self.arguments.get(self.index, prompt).unwrap();
-            let result_value = val.get_path();
+    // _string_multiple
 
-            self.ret(result_value)
-        } else {
-            // get value from REPL.
+    pub fn string_multiple(&mut self, prompt: &str) -> Result<arg_type::StringMutiple, String> {
+        let parse = |x: &str| {
+            let result_value = crate::helper::parse_arg_string(x);
+            Ok((
+                result_value.clone(),
+                arg_type::ReplArg::StringMultiple(result_value),
+            ))
+        };
 
-            let str = DialogerWraper::get_string(prompt, &self.theme);
+        self.answer(
+            prompt,
+            |theme, options| Self::ask_text(prompt, theme, options, parse),
+            |x| x.get_string_multiple().map(|x| x.to_vec()),
+            parse,
+        )
+    }
 
-            let result_value = Path::new(&str).to_path_buf();
+    // _number
+    pub fn number(&mut self, prompt: &str) -> Result<i64, String> {
+        let parse = |x: &str| {
+            let result_value = parse_number(x)?;
+            Ok((result_value, arg_type::ReplArg::Number(result_value)))
+        };
 
-            self.arguments
-                .add(self.index, prompt, arg_type::ReplArg::String(str));
-            // self.ret(result_value)
-            self.ret(Ok(result_value))
-        }
+        self.answer(
+            prompt,
+            |theme, options| Self::ask_text(prompt, theme, options, parse),
+            |x| x.get_number(),
+            parse,
+        )
+    }
+    // _number_multiple
+
+    pub fn number_multiple(&mut self, prompt: &str) -> Result<arg_type::NumberMutiple, String> {
+        let parse = |x: &str| {
+            let result_value = crate::helper::parse_arg_string(x)
+                .iter()
+                .map(|x| parse_number(x))
+                .collect::<Result<Vec<arg_type::Number>, String>>()?;
+
+            Ok((
+                result_value.clone(),
+                arg_type::ReplArg::NumberMultiple(result_value),
+            ))
+        };
+
+        self.answer(
+            prompt,
+            |theme, options| Self::ask_text(prompt, theme, options, parse),
+            |x| x.get_number_multiple().map(|x| x.to_vec()),
+            parse,
+        )
+    }
+    // _yes_or_no
+    pub fn yes_or_no(&mut self, prompt: &str) -> Result<arg_type::Bool, String> {
+        let parse = |x: &str| {
+            let result_value = parse_bool(x)?;
+            Ok((result_value, arg_type::ReplArg::Bool(result_value)))
+        };
+
+        self.answer(
+            prompt,
+            |theme, options| {
+                let re = DialogerWraper::confirm(prompt, theme, options)?;
+                Ok(re.map(|x| (x, arg_type::ReplArg::Bool(x)))) // -> "true" or "false"
+            },
+            |x| x.get_bool(),
+            parse,
+        )
+    }
+
+    // _path
+    pub fn path(&mut self, prompt: &str) -> Result<arg_type::Path, String> {
+        let parse = |x: &str| {
+            Ok((
+                Path::new(x).to_path_buf(),
+                arg_type::ReplArg::String(x.to_string()),
+            ))
+        };
+
+        self.answer(
+            prompt,
+            |theme, options| Self::ask_text(prompt, theme, options, parse),
+            |x| x.get_path(),
+            parse,
+        )
     }
 
     // _path_multiple
     pub fn path_multiple(&mut self, prompt: &str) -> Result<arg_type::PathMutiple, String> {
-        if self.is_from_toml {
-            let result_value = self
-                .arguments
-                .get(self.index, prompt)
-                .unwrap()
-                .get_path_multiple();
-
-            self.ret(result_value)
-        } else {
-            let multiple_string = DialogerWraper::get_string_multiple(prompt, &self.theme);
+        let parse = |x: &str| {
+            let multiple_string = crate::helper::parse_arg_string(x);
 
             let result_value: arg_type::PathMutiple = multiple_string
                 .iter()
                 .map(|x| Path::new(&x).to_path_buf())
                 .collect();
 
-            self.arguments.add(
-                self.index,
-                prompt,
+            Ok((
+                result_value,
                 arg_type::ReplArg::StringMultiple(multiple_string),
-            );
-            self.ret(Ok(result_value))
-        }
+            ))
+        };
+
+        self.answer(
+            prompt,
+            |theme, options| Self::ask_text(prompt, theme, options, parse),
+            |x| x.get_path_multiple(),
+            parse,
+        )
     }
 
     // _select
     pub fn select(&mut self, prompt: &str, items: &Vec<&str>) -> Result<String, String> {
-        if !self.is_from_toml {
-            let str = DialogerWraper::get_single_selected(prompt, items, &self.theme);
-
-            let result_value = arg_type::ReplArg::String(str.to_string());
-
-            self.arguments.add(self.index, prompt, result_value);
-        }
-
-        let result_value = self.arguments.get(self.index, prompt).unwrap().get_string();
+        let parse = |x: &str| {
+            let str = parse_choice(x, items)?;
+            Ok((str.clone(), arg_type::ReplArg::String(str)))
+        };
 
-        // return
-        self.index += 1;
-        result_value
+        self.answer(
+            prompt,
+            |theme, options| {
+                let re = DialogerWraper::get_single_selected(prompt, items, theme, options)?;
+                Ok(re.map(|x| (x.to_string(), arg_type::ReplArg::String(x.to_string()))))
+            },
+            |x| x.get_string(),
+            parse,
+        )
     }
 
     // _select_multiple
@@ -271,88 +678,467 @@ impl DialogGenerator {
         prompt: &str,
         items: &Vec<&str>,
     ) -> Result<Vec<String>, String> {
-        if self.is_from_toml {
-            let result_value = self
-                .arguments
-                .get(self.index, prompt)
-                .unwrap()
-                .get_string_multiple();
+        let parse = |x: &str| {
+            let result_value = crate::helper::parse_arg_string(x)
+                .iter()
+                .map(|x| parse_choice(x, items))
+                .collect::<Result<Vec<String>, String>>()?;
+
+            Ok((
+                result_value.clone(),
+                arg_type::ReplArg::StringMultiple(result_value),
+            ))
+        };
+
+        self.answer(
+            prompt,
+            |theme, options| {
+                let re = DialogerWraper::get_multiple_selected(prompt, items, theme, options)?;
+
+                Ok(re.map(|x| {
+                    let result_value: Vec<String> = x.iter().map(|x| x.to_string()).collect();
+                    (
+                        result_value.clone(),
+                        arg_type::ReplArg::StringMultiple(result_value),
+                    )
+                }))
+            },
+            |x| x.get_string_multiple().map(|x| x.to_vec()),
+            parse,
+        )
+    }
+
+    // _editor
+    pub fn editor(&mut self, prompt: &str) -> Result<String, String> {
+        let parse = |x: &str| Ok((x.to_string(), arg_type::ReplArg::String(x.to_string())));
+
+        self.answer(
+            prompt,
+            |_theme, options| {
+                let text = options.initial_text.as_deref().unwrap_or(prompt);
+                let re = DialogerWraper::get_string_from_editor(text, options);
+                Ok(re.map(|x| (x.clone(), arg_type::ReplArg::String(x))))
+            },
+            |x| x.get_string(),
+            parse,
+        )
+    }
+
+    // _section
+    /// 一组问题, 答案保存在 toml 中名为 `name` 的表中.
+    /// 无论这一组中有多少个问题, 在外面都只占一个序号, 所以增加或者删除这一组中的问题不会影响后面的问题的 key.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("serve").action(Arg::Dialog(&|r| {
+    ///         let (_host, _port) = r
+    ///             .section("server", |r| Ok((r.string("主机")?, r.number("端口")?)))
+    ///             .unwrap();
+    ///     }));
+    /// ```
+    pub fn section<T>(
+        &mut self,
+        name: &str,
+        f: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let arguments = match self.arguments.remove_by_key(name) {
+            Some(arg_type::ReplArg::Table(x)) => x,
+            _ => ReplArgStore::new(),
+        };
+
+        let mut child = self.child(arguments, self.is_from_toml);
+        let result_value = f(&mut child);
+
+        self.join(&mut child);
+        self.answered.push(name.to_string());
+        self.arguments
+            .insert(name.to_string(), arg_type::ReplArg::Table(child.arguments));
+
+        self.ret(result_value)
+    }
 
-            self.ret(result_value.map(|x| x.to_vec()))
+    // _when
+    /// 只有 `condition` 为 `true` 时才提问的一组问题, 例如使用 TLS 时才需要证书的路径.
+    /// 和 `section()` 一样, 答案保存在 toml 中名为 `name` 的表中, 无论是否提问都只占一个序号,
+    /// 所以后面的问题的 key 不会因为是否提问而改变. 从 toml 读取答案时也只读取需要提问的组.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("serve").action(Arg::Dialog(&|r| {
+    ///         let use_tls = r.key("use_tls").yes_or_no("使用 TLS?").unwrap();
+    ///         let _cert = r.when(use_tls, "tls", |r| r.path("证书路径")).unwrap();
+    ///     }));
+    /// ```
+    pub fn when<T>(
+        &mut self,
+        condition: bool,
+        name: &str,
+        f: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<Option<T>, String> {
+        if condition {
+            self.section(name, f).map(Some)
         } else {
-            // get value from REPL.
+            self.arguments.remove_by_key(name);
+            self.ret(Ok(None))
+        }
+    }
 
-            let result_value: Vec<String> =
-                DialogerWraper::get_multiple_selected(prompt, items, &self.theme)
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect();
+    // _repeat
+    /// 重复提问一组问题, 每次提问之前询问 `prompt` 是否继续, 例如添加多个 host, 每个 host 有名称, 端口和用户名.
+    /// 第一次询问时回答否, 结果是空的 `Vec`.
+    /// 答案保存在 toml 中名为 `name` 的表数组 (`[[name]]`) 中, 和 `section()` 一样只占一个序号.
+    /// 从 toml 读取答案时, 表数组中有几个表就重复几次.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("hosts").action(Arg::Dialog(&|r| {
+    ///         let _hosts = r
+    ///             .repeat("hosts", "添加一个 host?", |r| {
+    ///                 Ok((r.key("name").string("名称")?, r.key("port").number("端口")?))
+    ///             })
+    ///             .unwrap();
+    ///     }));
+    /// ```
+    pub fn repeat<T>(
+        &mut self,
+        name: &str,
+        prompt: &str,
+        f: impl Fn(&mut Self) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let replayed = match self.arguments.remove_by_key(name) {
+            Some(arg_type::ReplArg::Tables(x)) if self.is_from_toml => Some(x),
+            // 没有添加时保存的是 `name = []`, 读取时是空的数组.
+            Some(arg_type::ReplArg::NumberMultiple(x)) if self.is_from_toml && x.is_empty() => {
+                Some(vec![])
+            }
+            _ => None,
+        };
+
+        if self.is_from_toml && replayed.is_none() {
+            if !self.is_interactive() && self.lines.is_none() {
+                let re = self.record(Err(missing_answer(name, prompt)));
+                return self.ret(re);
+            }
+
+            // toml 中没有这一组问题的答案, 启动问答式交互.
+            self.has_new_answers = true;
+        }
 
-            let arg = arg_type::ReplArg::StringMultiple(result_value.clone());
+        let mut stores: Vec<ReplArgStore> = vec![];
+        let mut result_value: Vec<T> = vec![];
 
-            self.arguments.add(self.index, prompt, arg);
+        let mut ask = |this: &mut Self, arguments: ReplArgStore, is_from_toml: bool| {
+            let mut child = this.child(arguments, is_from_toml);
+            let re = f(&mut child);
 
-            self.ret(Ok(result_value))
+            this.join(&mut child);
+            stores.push(child.arguments);
+            re.map(|x| result_value.push(x))
+        };
+
+        let re = match replayed {
+            Some(items) => items
+                .into_iter()
+                .try_for_each(|arguments| ask(self, arguments, true)),
+            None => loop {
+                let need_more = if self.lines.is_some() {
+                    let re = self.next_line(name, prompt).and_then(|x| {
+                        if x.trim().is_empty() {
+                            Ok(false)
+                        } else {
+                            parse_bool(&x)
+                        }
+                    });
+                    self.record(re)
+                } else {
+                    let options = QuestionOptions {
+                        default: Some("false".to_string()),
+                        ..Default::default()
+                    };
+                    // 不能返回上一个问题, 不会是 `None`.
+                    let re = DialogerWraper::confirm(prompt, &self.theme, &options);
+                    self.record(re.map(|x| x.unwrap_or_default()))
+                };
+
+                match need_more {
+                    Ok(true) => {}
+                    Ok(false) => break Ok(()),
+                    Err(err) => break Err(err),
+                }
+
+                if let Err(err) = ask(self, ReplArgStore::new(), false) {
+                    break Err(err);
+                }
+            },
+        };
+
+        self.answered.push(name.to_string());
+        self.arguments
+            .insert(name.to_string(), arg_type::ReplArg::Tables(stores));
+
+        self.ret(re.map(|_| result_value))
+    }
+
+    // _review
+    /// 回答完 `f` 中的所有问题之后列出所有答案, 可以选择其中一个重新回答, 确认之后才返回 `f` 的结果.
+    /// * 回答输入框类型的问题时, 输入 `:back` 返回上一个问题, 确认和选择类型的问题按 Esc 返回上一个问题,
+    ///   编辑器没有保存就关闭时返回上一个问题.
+    /// * `section()` `repeat()` 中的一组问题在列表中是一个整体, 选择之后整组重新回答,
+    ///   组中的问题不能返回上一个问题.
+    /// * 所有的答案都来自 toml 时不需要确认, 直接返回 `f` 的结果.
+    /// * stdin 不是终端时, 在最后一个问题之后读取一行: 空行或者没有更多的行表示确认, 否则是需要重新回答的问题的 key.
+    ///   也可以输入 `:back` 返回上一个问题.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("deploy").action(Arg::Dialog(&|r| {
+    ///         let (_env, _count) = r
+    ///             .review(|r| Ok((r.key("env").string("环境")?, r.key("count").number("实例数量")?)))
+    ///             .unwrap();
+    ///     }));
+    /// ```
+    pub fn review<T>(&mut self, f: impl Fn(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        if !self.is_interactive() && self.lines.is_none() {
+            return f(self);
         }
+
+        let index = self.index;
+        let is_from_toml = self.is_from_toml;
+        let reviewing = self.reviewing;
+        let start = self.answered.len();
+
+        self.reviewing = true;
+        if self.is_interactive() {
+            println!(
+                "{}",
+                format!("输入 {} 或者按 Esc 返回上一个问题", GO_BACK).dimmed()
+            );
+        }
+
+        let re = loop {
+            // 返回上一个问题时, `f` 中的 `unwrap()` 会 panic, 这里也需要捕获.
+            let re = catch_quiet_panic(|| f(self));
+            QUIET_PANIC.with(|x| x.set(self.error.is_some()));
+
+            if self.going_back {
+                self.going_back = false;
+                if self.answered.len() > start {
+                    if let Some(key) = self.answered.pop() {
+                        self.arguments.remove_by_key(&key);
+                    }
+                }
+            } else {
+                match re {
+                    Ok(Ok(result_value)) if is_from_toml && !self.has_new_answers => {
+                        // 所有的答案都来自 toml, 不需要确认.
+                        break Ok(result_value);
+                    }
+                    Ok(Ok(result_value)) => match self.pick_answer(start) {
+                        Ok(None) => break Ok(result_value),
+                        Ok(Some(key)) => {
+                            self.arguments.remove_by_key(&key);
+                        }
+                        Err(err) => break self.record(Err(err)),
+                    },
+                    Ok(Err(err)) => break Err(err),
+                    Err(payload) => std::panic::resume_unwind(payload),
+                }
+            }
+
+            // 使用已有的答案重新执行 `f`, 只提问被删除的答案.
+            self.index = index;
+            self.is_from_toml = true;
+            self.answered.truncate(start);
+            self.options = QuestionOptions::default();
+        };
+
+        self.is_from_toml = is_from_toml;
+        self.reviewing = reviewing;
+        re
     }
 
-    // _editor
-    pub fn editor(&mut self, prompt: &str) -> Result<String, String> {
-        if self.is_from_toml {
-            self.arguments
-                .get(self.index, prompt)
-                .unwrap_or_else(|| panic!("没找到需要的参数: {}", key_gen(self.index, prompt)));
+    /// 列出 `review()` 中的所有答案, 返回需要重新回答的问题的 key, 确认时返回 `None`.
+    /// 从 stdin 读取答案时读取一行: 空行或者没有更多的行表示确认, 否则是需要重新回答的问题的 key.
+    fn pick_answer(&mut self, start: usize) -> Result<Option<String>, String> {
+        let answers: Vec<String> = self.answered[start..].to_vec();
 
-            let result_value = self.arguments.get(self.index, prompt).unwrap().get_string();
+        if self.lines.is_some() {
+            let line = self.read_line().unwrap_or_default();
+            let key = line.trim();
 
-            return self.ret(result_value);
+            return if key.is_empty() {
+                Ok(None)
+            } else if answers.iter().any(|x| x == key) {
+                Ok(Some(key.to_string()))
+            } else {
+                Err(format!(
+                    "{}: {} 不是已经回答的问题, 可以选择: {:?}",
+                    "答案不正确".styled_error_marker(),
+                    key.styled_arg(),
+                    answers
+                ))
+            };
         }
 
-        let result_value = DialogerWraper::get_string_from_editor(prompt);
-        self.arguments.add(
-            self.index,
-            prompt,
-            arg_type::ReplArg::String(result_value.clone()),
-        );
+        let mut items = vec!["确认".to_string()];
+        items.extend(answers.iter().map(|key| {
+            let value = self
+                .arguments
+                .get_by_key(key)
+                .and_then(|x| toml::Value::try_from(x).ok())
+                .map(|x| x.to_string())
+                .unwrap_or_default();
+            format!("{} = {}", key, value)
+        }));
+
+        let selection = dialoguer::Select::with_theme(&self.theme)
+            .with_prompt("确认答案, 或者选择一个答案重新回答")
+            .items(&items)
+            .default(0)
+            .interact();
+
+        match selection {
+            Ok(0) => Ok(None),
+            Ok(i) => Ok(Some(answers[i - 1].clone())),
+            Err(_e) => Err(_e.to_string()),
+        }
+    }
+
+    /// `section()` `repeat()` 中的一组问题.
+    /// 从 stdin 读取答案时, 一组问题回答完之后需要使用 `join()` 继续读取后面的答案.
+    fn child(&mut self, arguments: ReplArgStore, is_from_toml: bool) -> Self {
+        Self {
+            arguments,
+            index: ARGUMENTS_START_INDEX,
+            is_from_toml,
+            theme: dialoguer::theme::ColorfulTheme::default(),
+            has_new_answers: false,
+            options: QuestionOptions::default(),
+            reviewing: false,
+            going_back: false,
+            answered: vec![],
+            interactive: self.interactive,
+            lines: self.lines.take(),
+            replay_hint: self.replay_hint.clone(),
+            line_questions: vec![],
+            error: None,
+        }
+    }
 
-        self.ret(Ok(result_value))
+    /// 一组问题回答完之后, 合并 `child()` 的状态.
+    fn join(&mut self, child: &mut Self) {
+        self.has_new_answers |= child.has_new_answers;
+        self.lines = child.lines.take();
+        if self.error.is_none() {
+            self.error = child.error.take();
+        }
     }
 
     // _password
-    /// 让用户手动输入密码.
+    /// 让用户手动输入密码, 密码不会保存到答案中.
+    /// stdin 不是终端时从 stdin 读取一行作为密码.
     pub fn password(&mut self, prompt: &str) -> Result<String, dialoguer::Error> {
-        // 密码不应该被输出到 self.arguments 里面.
+        let re = self.ask_password(prompt, |theme| DialogerWraper::password(prompt, theme));
 
-        DialogerWraper::password(prompt, &self.theme)
+        re.map_err(|err| dialoguer::Error::IO(std::io::Error::other(err)))
     }
 
     // _password_with_confirmation
+    /// 让用户输入两次密码, 密码不会保存到答案中.
+    /// stdin 不是终端时从 stdin 读取一行作为密码, 不需要确认.
+    /// 无法获取密码时 panic, 在 `Arg::Dialog` 中执行失败.
     pub fn password_with_confirmation(&mut self, prompt: &str) -> String {
-        _ = self;
+        let re = self.ask_password(prompt, |_theme| {
+            DialogerWraper::password_with_confirmation(prompt)
+        });
 
+        match re {
+            Ok(password) => password,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// 获取密码, 从 stdin 读取答案时读取一行, 无法使用问答式交互时返回错误.
+    fn ask_password(
+        &mut self,
+        prompt: &str,
+        ask: impl FnOnce(&dialoguer::theme::ColorfulTheme) -> Result<String, dialoguer::Error>,
+    ) -> Result<String, String> {
         // 密码不应该被输出到 self.arguments 里面.
-        DialogerWraper::password_with_confirmation(prompt)
+        self.options = QuestionOptions::default();
+
+        let re = if self.lines.is_some() {
+            self.next_line(prompt, "密码")
+        } else if !self.is_interactive() {
+            Err(format!(
+                "{}
+stdin 不是终端, 无法输入密码",
+                missing_answer(prompt, "密码")
+            ))
+        } else {
+            ask(&self.theme).map_err(|err| err.to_string())
+        };
+
+        self.record(re)
     }
 
-    /// 打印 快捷参数
+    /// 打印 快捷参数, 并询问是否把答案保存到文件中.
+    /// 下次可以使用 `app cmd <答案文件>` 或者 `app cmd stdin` 直接使用这些答案.
     pub fn finesh_and_print(&self, app_name: &str, command_name: &str) {
-        let app_name = app_name.cyan();
-        let command_name = command_name.bright_cyan();
+        let toml_str = self.to_toml().unwrap();
 
         println!(
             r#"
-Executed command: {app_name} {command_name} stdin << '{marker}'
-{toml_str}
+Executed command: {} {} stdin << '{marker}'
+{}
 {marker}
 "#,
-            toml_str = self.to_toml().unwrap().green(),
-            // marker = r#"###_marker_###"#,
-            marker = r#""""""""""""#,
+            app_name.cyan(),
+            command_name.bright_cyan(),
+            toml_str.green(),
+            marker = "EOF",
         );
+
+        if self.is_interactive() {
+            self.save_answers(app_name, command_name, &toml_str);
+        }
+    }
+
+    /// 询问是否把答案保存到文件中.
+    fn save_answers(&self, app_name: &str, command_name: &str, toml_str: &str) {
+        let need_save = dialoguer::Confirm::with_theme(&self.theme)
+            .with_prompt("把答案保存到文件中, 下次直接使用?")
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+        if !need_save {
+            return;
+        }
+
+        let path = dialoguer::Input::<String>::with_theme(&self.theme)
+            .with_prompt("文件路径")
+            .default(format!("{}.answers.toml", command_name))
+            .interact_text();
+
+        let path = match path {
+            Ok(x) => x,
+            Err(_e) => return eprintln!("{}", _e.red()),
+        };
+
+        match std::fs::write(&path, toml_str) {
+            Ok(_) => {
+                println!(
+                    "答案已保存到 {}, 下次可以执行: {} {} {}\n或者设置环境变量: {}",
+                    path.styled_arg(),
+                    app_name.cyan(),
+                    command_name.bright_cyan(),
+                    path.styled_arg(),
+                    format!("{}={}", answers_env_var(app_name, command_name), path).styled_arg(),
+                );
+            }
+            Err(_e) => eprintln!("{}: {} {}", "无法保存答案".styled_error_marker(), path, _e),
+        }
     }
 
     fn ret<T>(&mut self, result_value: T) -> T {
         self.index += 1;
+        self.options = QuestionOptions::default();
         result_value
     }
 }
@@ -360,44 +1146,85 @@ Executed command: {app_name} {command_name} stdin << '{marker}'
 // ------- REPL Functions -------
 
 /// 对 dialoguer crate 的二次封装.
-struct DialogerWraper();
+pub(crate) struct DialogerWraper();
 impl DialogerWraper {
-    fn get_string(prompt: &str, theme: &dialoguer::theme::ColorfulTheme) -> String {
-        let re = dialoguer::Input::<String>::with_theme(theme)
-            .with_prompt(prompt)
-            .interact_text();
+    /// 打印 dialoguer 的错误, 然后重新提问.
+    /// stdin 或者 stderr 不是终端时, 重新提问也会失败, 返回错误, 避免无限重复提问.
+    /// `Arg::Dialog` 在这种情况下会从 stdin 读取答案, 不会使用 dialoguer.
+    fn on_error(err: dialoguer::Error) -> Result<(), String> {
+        if !is_interactive() {
+            return Err(format!(
+                "{}: 不是终端, 无法使用问答式交互 ({})",
+                "无法继续".styled_error_marker(),
+                err
+            ));
+        }
 
-        match re {
-            Ok(s) => s,
+        eprintln!("{}", err.red());
+        Ok(())
+    }
+
+    pub(crate) fn get_string(
+        prompt: &str,
+        theme: &dialoguer::theme::ColorfulTheme,
+    ) -> Result<String, String> {
+        DialogerWraper::input(prompt, theme, &QuestionOptions::default())
+    }
+
+    /// 使用问题的选项的输入框.
+    fn input(
+        prompt: &str,
+        theme: &dialoguer::theme::ColorfulTheme,
+        options: &QuestionOptions,
+    ) -> Result<String, String> {
+        let mut input = dialoguer::Input::<String>::with_theme(theme)
+            .with_prompt(options.formated_prompt(prompt))
+            .allow_empty(options.allow_empty);
+
+        if let Some(default) = &options.default {
+            input = input.default(default.clone());
+        }
+        if let Some(text) = &options.initial_text {
+            input = input.with_initial_text(text);
+        }
+
+        match input.interact_text() {
+            Ok(s) => Ok(s),
             Err(_e) => {
-                eprintln!("{}", _e.red());
-                DialogerWraper::get_string(prompt, theme) // 继续本次问题
+                DialogerWraper::on_error(_e)?;
+                DialogerWraper::input(prompt, theme, options) // 继续本次问题
             }
         }
     }
 
-    fn get_string_multiple(prompt: &str, theme: &dialoguer::theme::ColorfulTheme) -> Vec<String> {
+    pub(crate) fn get_string_multiple(
+        prompt: &str,
+        theme: &dialoguer::theme::ColorfulTheme,
+    ) -> Result<Vec<String>, String> {
         let re = dialoguer::Input::<String>::with_theme(theme)
             .with_prompt(prompt)
             .interact_text();
 
         match re {
-            Ok(input) => crate::helper::parse_arg_string(&input),
+            Ok(input) => Ok(crate::helper::parse_arg_string(&input)),
             Err(_e) => {
-                eprintln!("{}", _e.red());
+                DialogerWraper::on_error(_e)?;
                 DialogerWraper::get_string_multiple(prompt, theme) // 继续本次问题
             }
         }
     }
 
-    fn get_number(prompt: &str, theme: &dialoguer::theme::ColorfulTheme) -> arg_type::Number {
-        let input = DialogerWraper::get_string(prompt, theme);
+    pub(crate) fn get_number(
+        prompt: &str,
+        theme: &dialoguer::theme::ColorfulTheme,
+    ) -> Result<arg_type::Number, String> {
+        let input = DialogerWraper::get_string(prompt, theme)?;
         let input = input.trim();
         // 用户说输入了某些东西
         let parse_result: Result<arg_type::Number, ParseIntError> = input.parse();
 
         match parse_result {
-            Ok(num) => num,
+            Ok(num) => Ok(num),
             Err(_e) => {
                 let err_message = format!("{}", _e).red().to_string();
                 eprintln!("{}", err_message);
@@ -409,85 +1236,132 @@ impl DialogerWraper {
         }
     }
 
-    fn get_bool(prompt: &str, theme: &dialoguer::theme::ColorfulTheme) -> bool {
-        let re = dialoguer::Confirm::with_theme(theme)
+    pub(crate) fn get_bool(
+        prompt: &str,
+        theme: &dialoguer::theme::ColorfulTheme,
+    ) -> Result<bool, String> {
+        // 不能返回上一个问题, 不会是 `None`.
+        DialogerWraper::confirm(prompt, theme, &QuestionOptions::default())
+            .map(|x| x.unwrap_or_default())
+    }
+
+    /// 可以返回上一个问题并且按了 Esc 时返回 `None`, 否则按 Esc 时继续本次问题.
+    fn confirm(
+        prompt: &str,
+        theme: &dialoguer::theme::ColorfulTheme,
+        options: &QuestionOptions,
+    ) -> Result<Option<bool>, String> {
+        let mut confirm = dialoguer::Confirm::with_theme(theme)
             // .with_prompt("Y 键 N 键选择, 回车键确认: ")
-            .with_prompt(prompt)
-            .wait_for_newline(true)
-            .interact();
+            .with_prompt(options.formated_prompt(prompt))
+            .wait_for_newline(true);
 
-        match re {
-            Ok(b) => b,
+        if let Some(default) = options.default.as_deref().and_then(|x| parse_bool(x).ok()) {
+            confirm = confirm.default(default);
+        }
+
+        match confirm.interact_opt() {
+            Ok(Some(b)) => Ok(Some(b)),
+            Ok(None) if options.can_go_back => Ok(None),
+            Ok(None) => DialogerWraper::confirm(prompt, theme, options), // 继续本次问题
             Err(_e) => {
-                eprintln!("{}", _e.red());
-                DialogerWraper::get_bool(prompt, theme) // 继续本次问题
+                DialogerWraper::on_error(_e)?;
+                DialogerWraper::confirm(prompt, theme, options) // 继续本次问题
             }
         }
     }
 
+    /// 可以返回上一个问题并且按了 Esc 时返回 `None`, 否则按 Esc 时继续本次问题.
     fn get_single_selected<'a, T>(
         prompt: &str,
         items: &'a [T],
         theme: &dialoguer::theme::ColorfulTheme,
-    ) -> &'a T
+        options: &QuestionOptions,
+    ) -> Result<Option<&'a T>, String>
     where
         T: ToString + Clone,
     {
+        let default = options
+            .default
+            .as_ref()
+            .and_then(|x| items.iter().position(|item| item.to_string() == *x))
+            .unwrap_or(0);
+
         let re = dialoguer::FuzzySelect::with_theme(theme)
-            .with_prompt(prompt)
+            .with_prompt(options.formated_prompt(prompt))
             .items(items)
-            .default(0)
-            .interact();
+            .default(default)
+            .interact_opt();
 
         match re {
-            Ok(selection) => &(items[selection]),
+            Ok(Some(selection)) => Ok(Some(&(items[selection]))),
+            Ok(None) if options.can_go_back => Ok(None),
+            Ok(None) => DialogerWraper::get_single_selected(prompt, items, theme, options), // 继续本次问题
             Err(_e) => {
-                eprintln!("{}", _e.red());
+                DialogerWraper::on_error(_e)?;
 
                 // 继续本次问题
-                DialogerWraper::get_single_selected(prompt, items, theme)
+                DialogerWraper::get_single_selected(prompt, items, theme, options)
             }
         }
     }
 
+    /// 可以返回上一个问题并且按了 Esc 时返回 `None`, 否则按 Esc 时继续本次问题.
     fn get_multiple_selected<'a, T>(
         prompt: &str,
         items: &'a [T],
         theme: &dialoguer::theme::ColorfulTheme,
+        options: &QuestionOptions,
         // ) -> Vec<T>
-    ) -> Vec<&'a T>
+    ) -> Result<Option<Vec<&'a T>>, String>
     where
         T: ToString + Clone,
     {
+        let defaults: Vec<String> = options
+            .default
+            .as_deref()
+            .map(crate::helper::parse_arg_string)
+            .unwrap_or_default();
+        let checked: Vec<bool> = items
+            .iter()
+            .map(|x| defaults.contains(&x.to_string()))
+            .collect();
+
         let re = dialoguer::MultiSelect::with_theme(theme)
             // .with_prompt("What do you choose?")
-            .with_prompt(prompt)
+            .with_prompt(options.formated_prompt(prompt))
             .items(items)
-            .interact();
+            .defaults(&checked)
+            .interact_opt();
 
         match re {
-            Ok(selection) => {
+            Ok(Some(selection)) => {
                 let mut seleted: Vec<&T> = vec![];
                 for i in selection {
                     seleted.push(&items[i]);
                 }
-                seleted
+                Ok(Some(seleted))
             }
+            Ok(None) if options.can_go_back => Ok(None),
+            Ok(None) => DialogerWraper::get_multiple_selected(prompt, items, theme, options), // 继续本次问题
             Err(_e) => {
-                eprintln!("{}", _e.red());
-                DialogerWraper::get_multiple_selected(prompt, items, theme) // 继续本次问题
+                DialogerWraper::on_error(_e)?;
+                DialogerWraper::get_multiple_selected(prompt, items, theme, options)
+                // 继续本次问题
             }
         }
     }
 
-    fn get_string_from_editor(prompt: &str) -> arg_type::String {
+    /// 可以返回上一个问题并且没有保存就关闭了编辑器时返回 `None`, 否则是空字符串.
+    fn get_string_from_editor(prompt: &str, options: &QuestionOptions) -> Option<arg_type::String> {
         let re = dialoguer::Editor::new().edit(prompt);
 
         match re {
-            Ok(ostr) => ostr.unwrap_or("".to_string()),
+            Ok(None) if options.can_go_back => None,
+            Ok(ostr) => Some(ostr.unwrap_or("".to_string())),
             Err(_e) => {
                 eprintln!("{}", _e.red());
-                "".to_string()
+                Some("".to_string())
                 // panic!("{}", _e.red());
             }
         }
@@ -517,29 +1391,305 @@ impl DialogerWraper {
         re
     }
 
-    fn password_with_confirmation(prompt: &str) -> String {
+    fn password_with_confirmation(prompt: &str) -> Result<String, dialoguer::Error> {
         println!("{}", prompt.bright_green());
 
-        let re = dialoguer::Password::new()
+        dialoguer::Password::new()
             .with_prompt(if prompt.is_empty() {
                 "New Password"
             } else {
                 prompt
             })
             .with_confirmation("Confirm password", "Passwords mismatching")
-            .interact();
-        match re {
-            Ok(password) => password,
-            Err(_e) => panic!("{}", _e.red()),
-        }
+            .interact()
+    }
+}
+
+/// stdin 和 stderr 都是终端时才可以使用问答式交互, dialoguer 在 stderr 中显示问题.
+pub(crate) fn is_interactive() -> bool {
+    use std::io::IsTerminal;
+
+    std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// 执行 `Arg::Dialog` 的 action, 返回问答中的第一个错误.
+/// action 中通常使用 `unwrap()`, 问答出错时会 panic, 这里把它转换为错误, 避免程序以 101 退出.
+/// 和问答无关的 panic 不受影响.
+pub(crate) fn run_action(
+    f: &dyn Fn(&mut DialogGenerator),
+    repl: &mut DialogGenerator,
+) -> Result<(), String> {
+    let re = catch_quiet_panic(|| f(repl));
+
+    match (re, repl.error.take()) {
+        (_, Some(err)) => Err(err),
+        (Ok(_), None) => Ok(()),
+        (Err(payload), None) => std::panic::resume_unwind(payload),
+    }
+}
+
+/// 执行 `f` 并捕获其中的 panic.
+/// `QUIET_PANIC` 为 `true` 时不打印 panic 信息, 执行完之后重置 `QUIET_PANIC`.
+fn catch_quiet_panic<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    static HOOK: std::sync::Once = std::sync::Once::new();
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !QUIET_PANIC.with(|x| x.get()) {
+                previous(info);
+            }
+        }));
+    });
+
+    let re = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    QUIET_PANIC.with(|x| x.set(false));
+    re
+}
+
+/// toml 中没有这个问题的答案, 并且无法通过问答式交互获取.
+fn missing_answer(key: &str, prompt: &str) -> String {
+    format!(
+        "{}: {} ({})",
+        "缺少答案".styled_error_marker(),
+        key.styled_arg(),
+        prompt
+    )
+}
+
+/// 把文字形式的答案转换为数字.
+fn parse_number(input: &str) -> Result<arg_type::Number, String> {
+    input.trim().parse().map_err(|_e: ParseIntError| {
+        format!(
+            "{}, 需要输入一个数字, 示例: {}",
+            _e.red(),
+            "123".styled_arg()
+        )
+    })
+}
+
+/// 把文字形式的答案转换为 bool.
+fn parse_bool(input: &str) -> Result<arg_type::Bool, String> {
+    match input.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" => Ok(true),
+        "false" | "no" | "n" => Ok(false),
+        _ => Err(format!(
+            "需要 {} 或者 {}, 实际是: {}",
+            "true".styled_arg(),
+            "false".styled_arg(),
+            input
+        )),
+    }
+}
+
+/// 检查文字形式的答案是否是其中一个选项.
+fn parse_choice(input: &str, items: &[&str]) -> Result<String, String> {
+    if items.contains(&input) {
+        Ok(input.to_string())
+    } else {
+        Err(format!(
+            "{} 不是可选项, 可选项: {:?}",
+            input.styled_arg(),
+            items
+        ))
     }
 }
 
 #[cfg(test)]
 mod test_dialog {
-    // 这里面都是一些 dialoguer 式交互, 需要手动来测试.
+    // 这里面大多是一些 dialoguer 式交互, 需要手动来测试.
+
+    use super::*;
+
+    /// 从 `input` 每行读取一个答案.
+    fn from_lines(input: &str) -> DialogGenerator {
+        DialogGenerator::new_from_lines(
+            Box::new(std::io::Cursor::new(input.to_string())),
+            "replay hint".to_string(),
+        )
+    }
+
+    /// 从 `toml` 读取答案, 不管是否在终端中运行, 都按不是终端的情况处理.
+    fn from_toml(toml: &str) -> DialogGenerator {
+        let mut r = DialogGenerator::new_from_toml(toml).unwrap();
+        r.interactive = false;
+        r
+    }
+
+    #[test]
+    fn answers_from_lines() {
+        let mut r = from_lines("app\n\na b\ny\ny\nh1\ny\nh2\nn\n\n");
+
+        assert_eq!(r.key("name").string("名称").unwrap(), "app");
+        assert_eq!(r.key("port").default_answer(80).number("端口").unwrap(), 80);
+        assert_eq!(r.string_multiple("标签").unwrap(), vec!["a", "b"]);
+        assert!(r.yes_or_no("确认?").unwrap());
+
+        let hosts = r.repeat("hosts", "添加一个?", |r| r.key("host").string("host"));
+        assert_eq!(hosts.unwrap(), vec!["h1", "h2"]);
+
+        // 第一次询问时回答否.
+        let ports = r.repeat("ports", "添加一个?", |r| r.key("port").number("端口"));
+        assert_eq!(ports.unwrap(), Vec::<i64>::new());
+        assert!(r.error.is_none());
+
+        // 保存的 toml 中没有添加的表数组可以再次使用.
+        let saved = r.to_toml().unwrap();
+        let mut r = DialogGenerator::new_from_toml(&saved).unwrap();
+        let ports = r.repeat("ports", "添加一个?", |r| r.key("port").number("端口"));
+        assert_eq!(ports.unwrap(), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn missing_answer_from_toml() {
+        let mut r = from_toml("name = \"app\"");
+        assert_eq!(r.key("name").string("名称").unwrap(), "app");
 
-    // use super::*;
+        // 不是终端时, toml 中没有的答案返回错误, 不会 panic.
+        let err = r.key("port").number("端口").unwrap_err();
+        assert!(err.contains("port"));
+
+        // 有默认答案时使用默认答案, 并保存到 toml 中.
+        assert_eq!(r.key("port").default_answer(8080).number("端口"), Ok(8080));
+        assert!(r.to_toml().unwrap().contains("port = 8080"));
+    }
+
+    #[test]
+    fn missing_answer_from_lines() {
+        let mut r = from_lines("app\n");
+
+        assert_eq!(r.key("name").string("名称").unwrap(), "app");
+        let err = r.key("port").number("端口").unwrap_err();
+        assert!(err.contains("1. name (名称)"));
+        assert!(err.contains("2. port (端口)  <- 缺少答案"));
+        assert!(err.contains("replay hint"));
+        assert_eq!(r.error, Some(err));
+    }
+
+    #[test]
+    fn review_from_lines() {
+        let ask = |r: &mut DialogGenerator| -> Result<(String, i64), String> {
+            Ok((r.key("name").string("名称")?, r.key("port").number("端口")?))
+        };
+
+        // 回答完之后重新回答 port, 然后确认.
+        let mut r = from_lines("app\n80\nport\n8080\n\n");
+        assert_eq!(r.review(ask).unwrap(), ("app".to_string(), 8080));
+        assert!(r.to_toml().unwrap().contains("port = 8080"));
+
+        // 所有的答案都来自 toml 时不需要确认, 不会读取 "port".
+        let mut r = DialogGenerator::new_from_toml("name = \"app\"\nport = 80").unwrap();
+        r.lines = Some(Box::new(std::io::Cursor::new("port\n9090\n".to_string())));
+        assert_eq!(r.review(ask).unwrap(), ("app".to_string(), 80));
+    }
+
+    #[test]
+    fn review_from_toml() {
+        // 设置 key 之前保存的 toml.
+        let toml = r#"
+"01_环境" = "dev"
+"02_实例数量" = 3
+"#;
+
+        let mut r = from_toml(toml);
+        let re = r.review(|r| Ok((r.key("env").string("环境")?, r.number("实例数量")?)));
+        assert_eq!(re.unwrap(), ("dev".to_string(), 3));
+
+        // 答案改为保存在设置的 key 中.
+        let saved = r.to_toml().unwrap();
+        assert!(saved.contains("env = \"dev\""));
+        assert!(!saved.contains("01_环境"));
+    }
+
+    #[test]
+    fn go_back_from_lines() {
+        // 在 port 输入 :back, 返回上一个问题 name, 即使修改了错误信息.
+        let mut r = from_lines("app\n:back\nweb\n80\n\n");
+        let re = r.review(|r| {
+            let name = r.key("name").string("名称")?;
+            let port = r
+                .key("port")
+                .number("端口")
+                .map_err(|err| format!("port: {err}"))?;
+            Ok((name, port))
+        });
+        assert_eq!(re.unwrap(), ("web".to_string(), 80));
+
+        // 使用 unwrap() 时也可以返回上一个问题.
+        let mut r = from_lines("app\n:back\nweb\n80\n\n");
+        let re = r.review(|r| {
+            Ok((
+                r.key("name").string("名称").unwrap(),
+                r.key("port").number("端口").unwrap(),
+            ))
+        });
+        assert_eq!(re.unwrap(), ("web".to_string(), 80));
+        assert!(r.error.is_none());
+
+        // 确认和选择类型的问题也可以返回上一个问题.
+        let mut r = from_lines("app\n:back\nweb\n:back\ndb\nprod\ny\n\n");
+        let re = r.review(|r| {
+            Ok((
+                r.key("name").string("名称")?,
+                r.key("env").select("环境", &vec!["dev", "prod"])?,
+                r.key("tls").yes_or_no("TLS?")?,
+            ))
+        });
+        assert_eq!(re.unwrap(), ("db".to_string(), "prod".to_string(), true));
+    }
+
+    #[test]
+    fn run_action_returns_first_error() {
+        let mut r = from_lines("");
+        let re = run_action(
+            &|r| {
+                r.key("name").string("名称").unwrap();
+            },
+            &mut r,
+        );
+        assert!(re.unwrap_err().contains("缺少答案"));
+
+        // 和问答无关的 panic 不会被转换为错误.
+        let re = std::panic::catch_unwind(|| {
+            run_action(&|_r| panic!("bug"), &mut DialogGenerator::new())
+        });
+        assert!(re.is_err());
+    }
+
+    #[test]
+    fn invalid_regex_fails() {
+        // 正则表达式不正确时执行失败, 不会读取答案也不会 panic.
+        let mut r = from_lines("app\n");
+        let re = run_action(
+            &|r| {
+                r.key("name")
+                    .validate(Validator::Regex("("))
+                    .string("名称")
+                    .unwrap();
+            },
+            &mut r,
+        );
+        assert!(re.unwrap_err().contains("不正确的正则表达式"));
+    }
+
+    #[test]
+    fn password_from_lines() {
+        let mut r = from_lines("bob\nsecret\nsecret2\n");
+
+        assert_eq!(r.key("user").string("用户").unwrap(), "bob");
+        assert_eq!(r.password("密码").unwrap(), "secret");
+        assert_eq!(r.password_with_confirmation("新密码"), "secret2");
+        // 密码不会保存到答案中.
+        assert!(!r.to_toml().unwrap().contains("secret"));
+
+        // 没有更多的行时, 执行失败而不是 panic.
+        let re = run_action(
+            &|r| {
+                r.password("密码").unwrap();
+            },
+            &mut r,
+        );
+        assert!(re.unwrap_err().contains("4. 密码 (密码)  <- 缺少答案"));
+    }
 
     // #[test]
     // fn test_get_string() {
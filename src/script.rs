@@ -0,0 +1,119 @@
+//! `App::run_script()` 需要用到的类型.
+
+use crate::helper::*;
+use crate::DidHandled;
+use owo_colors::OwoColorize;
+use prettytable::{row, table};
+
+/// 脚本中的一行命令.
+#[derive(Clone, Debug)]
+pub(crate) struct ScriptLine {
+    /// 从 1 开始的行号.
+    pub line_number: usize,
+
+    /// 这一行的内容.
+    pub command: String,
+}
+
+/// 解析脚本文件的内容.
+/// 空行和以 `#` 开头的注释会被忽略.
+pub(crate) fn parse_script(content: &str) -> Vec<ScriptLine> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| ScriptLine {
+            line_number: i + 1,
+            command: line.trim().to_string(),
+        })
+        .collect()
+}
+
+/// 脚本中一行命令的执行结果.
+pub(crate) struct ScriptLineResult {
+    pub line: ScriptLine,
+    pub result: DidHandled,
+}
+
+/// 整个脚本的执行结果.
+pub(crate) struct ScriptResult {
+    pub script_name: String,
+    pub results: Vec<ScriptLineResult>,
+
+    /// 因为发生错误而没有执行的行数.
+    pub skipped: usize,
+}
+
+impl ScriptResult {
+    pub fn is_success(&self) -> bool {
+        self.skipped == 0 && self.results.iter().all(|x| x.result == DidHandled::Handled)
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|x| x.result != DidHandled::Handled)
+            .count()
+    }
+
+    /// 每一行命令的执行结果.
+    pub fn formated_massage(&self) -> String {
+        let mut table = table!();
+        table.set_format(result_table_formater());
+
+        let ok = if self.is_success() {
+            "ok".green().to_string()
+        } else {
+            "FAILED".red().to_string()
+        };
+
+        table.set_titles(row![format!(
+            "script {} ... {ok}    {} passed; {} failed; {} skipped",
+            self.script_name.styled_sub_command(),
+            self.results.len() - self.failed_count(),
+            self.failed_count(),
+            self.skipped,
+        )]);
+
+        for x in &self.results {
+            let state = match &x.result {
+                DidHandled::Handled => "ok".green().to_string(),
+                DidHandled::Failed(_) | DidHandled::FailedWithCode(..) => {
+                    "FAILED".red().to_string()
+                }
+            };
+
+            table.add_row(row![format!(
+                "{:>4}  {state}  {}",
+                x.line.line_number,
+                x.line.command.styled_arg()
+            )]);
+
+            if let DidHandled::Failed(err) | DidHandled::FailedWithCode(_, err) = &x.result {
+                table.add_row(row![err.trim()]);
+            }
+        }
+
+        format!("\n{}", table)
+    }
+}
+
+#[test]
+fn test_parse_script() {
+    let content = r#"
+# 注释
+app build true
+
+    # 缩进的注释
+app run "a b"
+"#;
+
+    let lines = parse_script(content);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].line_number, 3);
+    assert_eq!(lines[0].command, "app build true");
+    assert_eq!(lines[1].line_number, 6);
+}
@@ -0,0 +1,195 @@
+//! `app shell` 交互式 shell 需要用到的 history 和 completion.
+
+use crate::{Arg, SubCommand};
+use std::{
+    collections::VecDeque,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+// ------- History -------
+
+/// 保存在文件中的 history, 每行一条命令.
+/// 文件无法读写时, history 只保存在内存中.
+pub(crate) struct FileHistory {
+    /// 最新的命令在最前面.
+    deque: VecDeque<String>,
+
+    file: Option<PathBuf>,
+}
+
+impl FileHistory {
+    pub fn load(file: Option<PathBuf>) -> Self {
+        let mut deque = VecDeque::new();
+
+        if let Some(path) = &file {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                for line in content.lines().filter(|x| !x.trim().is_empty()) {
+                    deque.push_front(line.to_string());
+                }
+            }
+        }
+
+        Self { deque, file }
+    }
+}
+
+impl dialoguer::History<String> for FileHistory {
+    fn read(&self, pos: usize) -> Option<String> {
+        self.deque.get(pos).cloned()
+    }
+
+    fn write(&mut self, val: &String) {
+        if val.trim().is_empty() || self.deque.front() == Some(val) {
+            return;
+        }
+
+        self.deque.push_front(val.to_owned());
+
+        if let Some(path) = &self.file {
+            let re = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut f| writeln!(f, "{}", val));
+
+            if let Err(_e) = re {
+                // 写入失败时只保存在内存中.
+                self.file = None;
+            }
+        }
+    }
+}
+
+// ------- Completion -------
+
+/// 按 Tab 键时补全子命令的名称, 以及 `Arg::Path` `Arg::PathMutiple` 的路径.
+pub(crate) struct ShellCompletion {
+    /// 子命令的名称和 short_name.
+    names: Vec<String>,
+
+    /// 参数类型是 path 的子命令的名称和 short_name.
+    path_commands: Vec<String>,
+}
+
+impl ShellCompletion {
+    pub fn new(commands: &[SubCommand]) -> Self {
+        let mut names = vec![];
+        let mut path_commands = vec![];
+
+        for x in commands.iter().filter(|x| !x._hidden) {
+            let is_path = matches!(x.arg(), Arg::Path(_) | Arg::PathMutiple(_));
+
+            for name in [&x._cmd_name, &x._short_name] {
+                if name.is_empty() {
+                    continue;
+                }
+                names.push(name.clone());
+                if is_path {
+                    path_commands.push(name.clone());
+                }
+            }
+        }
+
+        names.push("help".to_string());
+        names.push("exit".to_string());
+        names.sort();
+
+        Self {
+            names,
+            path_commands,
+        }
+    }
+}
+
+impl dialoguer::Completion for ShellCompletion {
+    fn get(&self, input: &str) -> Option<String> {
+        match input.rsplit_once(' ') {
+            None => {
+                // 补全子命令的名称
+                let matched: Vec<String> = self
+                    .names
+                    .iter()
+                    .filter(|x| x.starts_with(input))
+                    .cloned()
+                    .collect();
+
+                common_prefix(&matched)
+            }
+            Some((head, last)) => {
+                let cmd_name = input.split(' ').next().unwrap_or_default();
+                if !self.path_commands.iter().any(|x| x == cmd_name) {
+                    return None;
+                }
+
+                complete_path(last).map(|x| format!("{} {}", head, x))
+            }
+        }
+    }
+}
+
+/// 补全文件或者文件夹的路径.
+fn complete_path(prefix: &str) -> Option<String> {
+    let (dir, file_prefix) = match prefix.rfind('/') {
+        Some(i) => (&prefix[..=i], &prefix[i + 1..]),
+        None => ("", prefix),
+    };
+
+    let read_dir = std::fs::read_dir(if dir.is_empty() {
+        Path::new(".")
+    } else {
+        Path::new(dir)
+    })
+    .ok()?;
+
+    let matched: Vec<String> = read_dir
+        .filter_map(|x| x.ok())
+        .filter_map(|x| {
+            let name = x.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(file_prefix) {
+                return None;
+            }
+
+            if x.path().is_dir() {
+                Some(name + "/")
+            } else {
+                Some(name)
+            }
+        })
+        .collect();
+
+    common_prefix(&matched).map(|x| format!("{}{}", dir, x))
+}
+
+/// 所有字符串的公共前缀, 没有字符串时返回 None.
+fn common_prefix(arr: &[String]) -> Option<String> {
+    let first = arr.first()?;
+
+    let mut re = first.clone();
+    for x in &arr[1..] {
+        while !x.starts_with(&re) {
+            re.pop();
+        }
+    }
+
+    Some(re)
+}
+
+#[test]
+fn test_shell_completion() {
+    use dialoguer::Completion;
+
+    let commands = vec![
+        SubCommand::create_an_sub_command("build").short_name("b"),
+        SubCommand::create_an_sub_command("bench"),
+        SubCommand::create_an_sub_command("open").action(Arg::Path(&|_x| {})),
+    ];
+
+    let c = ShellCompletion::new(&commands);
+    assert_eq!(c.get("bu"), Some("build".to_string()));
+    assert_eq!(c.get("be"), Some("bench".to_string()));
+    assert_eq!(c.get("b"), Some("b".to_string()));
+    assert_eq!(c.get("x"), None);
+    assert_eq!(c.get("open src/li"), Some("open src/lib.rs".to_string()));
+    assert_eq!(c.get("build src/li"), None);
+}
@@ -3,8 +3,11 @@ use std::{default, vec};
 use crate::{
     action::{Arg, ParseResult, SubcommandArgsValue},
     application::NeedTo,
+    each::{self, EachResult, ItemAction, ItemResult},
     examples_types::{Examples, SingleExample},
     helper::*,
+    hook::{self, Hook, HookFn},
+    question_and_anser::DialogerWraper,
 };
 
 use super::*;
@@ -43,6 +46,88 @@ pub struct SubCommand {
     /// 子命令需要的参数的类型以及该子命令的 action.
     /// 在打印子命令的帮助文档时需要用到此属性.
     _arg_type_with_action: Arg,
+
+    /// 此子命令在 app 帮助文档中所属的分组.
+    /// 空字符串表示没有分组.
+    pub(crate) _group: String,
+
+    /// 隐藏的子命令不会出现在 app 的帮助文档中, 但是依然可以被执行.
+    pub(crate) _hidden: bool,
+
+    /// 已弃用的子命令, 执行时会在 stderr 打印警告.
+    pub(crate) _deprecated: Option<Deprecation>,
+
+    /// 执行 action 之前的 hook.
+    pub(crate) _before_hooks: Vec<Hook>,
+
+    /// 执行 action 之后的 hook.
+    pub(crate) _after_hooks: Vec<Hook>,
+
+    /// 通过 `App::mount()` 挂载的 App.
+    /// 执行此子命令时, 剩下的参数都会交给这个 App 处理.
+    pub(crate) _mounted_app: Option<Box<App>>,
+
+    /// multi-call 模式下, 通过 `app_name` 以外的名称 (例如软链接) 直接执行此子命令时的名称.
+    /// 此时帮助文档中的 Usage 是 `{invoked_as} [arguments]`.
+    pub(crate) _invoked_as: Option<String>,
+
+    /// 危险的子命令, 执行 action 之前需要用户确认.
+    pub(crate) _dangerous: bool,
+
+    /// 逐个参数执行的 action, 设置后代替 `_arg_type_with_action` 中的 action.
+    _each: Option<ItemAction>,
+
+    /// 逐个参数执行时, 最多同时执行的数量.
+    _workers: usize,
+}
+
+/// `app --list-all-commands toml` 中的一个子命令.
+#[derive(serde::Serialize, Clone, Debug)]
+pub(crate) struct CommandInfo {
+    pub name: String,
+    pub short_name: String,
+    pub arg_type: String,
+    pub about: String,
+    pub group: String,
+    pub examples: usize,
+    pub deprecated: bool,
+}
+
+/// 子命令的弃用信息.
+#[derive(Clone, Debug)]
+pub(crate) struct Deprecation {
+    /// 用来替代此子命令的新命令的名称.
+    replacement: String,
+
+    /// 额外的说明.
+    message: String,
+}
+
+impl Deprecation {
+    fn formated(&self, app_name: &str, cmd_name: &str) -> String {
+        let mut re = format!(
+            "{}: {} {} 已弃用",
+            "warning".bright_yellow(),
+            app_name.styled_sub_command(),
+            cmd_name.styled_sub_command(),
+        );
+
+        if !self.replacement.is_empty() {
+            re += &format!(
+                ", 请使用 {} {} 代替",
+                app_name.styled_sub_command(),
+                self.replacement.styled_sub_command()
+            );
+        }
+
+        if !self.message.is_empty() {
+            re += &format!(".\n{}", self.message);
+        } else {
+            re += ".";
+        }
+
+        re
+    }
 }
 
 impl SubCommand {
@@ -70,6 +155,16 @@ impl SubCommand {
             _short_name: "".to_owned(),
             _exaples: Examples::new(),
             _arg_type_with_action: Arg::default(),
+            _group: String::new(),
+            _hidden: false,
+            _deprecated: None,
+            _before_hooks: vec![],
+            _after_hooks: vec![],
+            _mounted_app: None,
+            _invoked_as: None,
+            _dangerous: false,
+            _each: None,
+            _workers: 1,
         }
     }
 }
@@ -110,10 +205,138 @@ impl<'a> SubCommand {
     pub fn action(self, need_arg_type: Arg) -> Self {
         let mut re = self;
         re._arg_type_with_action = need_arg_type;
+        re._each = None;
+
+        re
+    }
+
+    /// 设置逐个参数执行的 action, 类似 `cargo add A B C` 相当于依次执行 `cargo add A` `cargo add B` `cargo add C`.
+    /// 参数的类型分别对应 `Arg::StringMutiple` `Arg::NumberMutiple` `Arg::PathMutiple` `Arg::BoolMutiple`.
+    /// 执行完所有参数后打印每个参数的执行结果, 只要有一个参数执行失败, 程序的退出码就是 1.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("add")
+    ///         .action_each(ItemAction::String(&|name| {
+    ///             println!("add {}", name);
+    ///             Ok(())
+    ///         }))
+    ///         .parallel(4);
+    /// ```
+    pub fn action_each(self, action: ItemAction) -> Self {
+        let mut re = self;
+        re._arg_type_with_action = match action {
+            ItemAction::String(_) => Arg::StringMutiple(&|_x| {}),
+            ItemAction::Number(_) => Arg::NumberMutiple(&|_x| {}),
+            ItemAction::Path(_) => Arg::PathMutiple(&|_x| {}),
+            ItemAction::Bool(_) => Arg::BoolMutiple(&|_x| {}),
+        };
+        re._each = Some(action);
+
+        re
+    }
+
+    /// 使用 `action_each()` 时, 最多同时执行 `workers` 个参数, 默认是 1, 也就是依次执行.
+    pub fn parallel(self, workers: usize) -> Self {
+        let mut re = self;
+        re._workers = workers.max(1);
+
+        re
+    }
+
+    /// 设置此子命令在 `app -h` 中所属的分组, 例如 "Project", "Maintenance".
+    /// 分组的显示顺序请使用 `App::group_order()` 设置.
+    pub fn group(self, group: &'a str) -> Self {
+        let mut re = self;
+        re._group = group.to_owned();
+
+        re
+    }
+
+    /// 隐藏此子命令.
+    /// 隐藏的子命令不会出现在 `app -h` 中, 但是依然可以被执行.
+    pub fn hidden(self, is_hidden: bool) -> Self {
+        let mut re = self;
+        re._hidden = is_hidden;
 
         re
     }
 
+    /// 标记此子命令是危险的, 例如删除文件, 清空数据库.
+    /// 执行 action 之前会显示解析后的参数, 需要用户确认.
+    /// 使用 `app --yes <command>` 或者设置环境变量 `<APP_NAME>_YES=1` 可以跳过确认.
+    /// stdin 不是终端且没有跳过确认时, 执行失败.
+    ///
+    /// 确认在 before hook 和 middleware 之后进行, 用户取消时它们已经执行, after hook 仍然会执行.
+    /// `Arg::Dialog` 的问题和 action 在同一个函数中, 无法在回答之后再确认:
+    /// 使用答案文件时会显示答案文件中的答案, 问答式交互时在提问之前确认.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("clean")
+    ///         .dangerous(true)
+    ///         .action(Arg::PathMutiple(&|_x| {}));
+    /// ```
+    pub fn dangerous(self, is_dangerous: bool) -> Self {
+        let mut re = self;
+        re._dangerous = is_dangerous;
+
+        re
+    }
+
+    /// 标记此子命令已弃用.
+    /// 执行此子命令时会在 stderr 打印警告, `app cmd -h` 中也会显示弃用信息.
+    /// * `replacement`: 用来替代此子命令的新命令的名称, 可以是空字符串.
+    /// * `message`: 额外的说明, 可以是空字符串.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("compile")
+    ///         .hidden(true)
+    ///         .deprecated("build", "compile 将在下一个版本中移除.")
+    ///         .action(Arg::Empty(&|_x| {}));
+    /// ```
+    pub fn deprecated(self, replacement: &'a str, message: &'a str) -> Self {
+        let mut re = self;
+        re._deprecated = Some(Deprecation {
+            replacement: replacement.to_owned(),
+            message: message.to_owned(),
+        });
+
+        re
+    }
+
+    /// 添加执行此子命令的 action 之前的 hook, 可以多次调用.
+    /// hook 的参数是子命令的名称和子命令接收到的参数, 返回 `Err` 时不会执行 action.
+    /// `-h` `-e` 和只解析不执行时不会调用 hook.
+    /// ```
+    /// use chenbao_cmd::*;
+    ///     cmd!("deploy")
+    ///         .before(&|name, args| {
+    ///             println!("start {} {:?}", name, args);
+    ///             Ok(())
+    ///         })
+    ///         .action(Arg::Empty(&|_x| {}));
+    /// ```
+    pub fn before(self, hook: &'static HookFn) -> Self {
+        let mut re = self;
+        re._before_hooks.push(Hook(hook));
+
+        re
+    }
+
+    /// 添加执行此子命令的 action 之后的 hook, 可以多次调用.
+    /// 即使参数解析失败也会调用, 返回 `Err` 时此子命令的执行结果是失败.
+    /// `-h` `-e` 和只解析不执行时不会调用 hook.
+    pub fn after(self, hook: &'static HookFn) -> Self {
+        let mut re = self;
+        re._after_hooks.push(Hook(hook));
+
+        re
+    }
+
+    /// 子命令需要的参数的类型以及该子命令的 action.
+    pub(crate) fn arg(&self) -> &Arg {
+        &self._arg_type_with_action
+    }
+
     // pub(crate) fn sub_command_run(&self, app_name: &str, cmd_args: SharedVecString) -> DidHandled {
     //     self.sub_command_try_run(app_name, cmd_args, NeedTo::Run)
     // }
@@ -130,11 +353,7 @@ impl SubCommand {
 
         let table = helper::vec_row_to_table(arr);
         if !table.is_empty() {
-            println!(
-                "子命令 {} {} 的使用示例:",
-                app_name,
-                self._cmd_name.styled_sub_command()
-            );
+            println!("子命令 {} 的使用示例:", self.formated_invocation(app_name));
             println!();
             println!("{}", table);
         }
@@ -142,6 +361,216 @@ impl SubCommand {
 }
 
 impl<'a> SubCommand {
+    /// 用户执行此子命令时输入的命令, 例如 `app cmd`.
+    /// multi-call 模式下是 `invoked_as`.
+    fn formated_invocation(&self, app_name: &str) -> String {
+        match &self._invoked_as {
+            Some(invoked_as) => invoked_as.styled_sub_command(),
+            None => format!(
+                "{} {}",
+                app_name.styled_sub_command(),
+                self._cmd_name.styled_sub_command()
+            ),
+        }
+    }
+
+    /// 危险的子命令在执行 action 之前请求用户确认.
+    /// * `args`: 解析后的参数, 显示给用户.
+    fn confirm_dangerous(&self, app_name: &str, args: &str, need_to: NeedTo) -> DidHandled {
+        use std::io::IsTerminal;
+
+        if !self._dangerous || !need_to.need_confirm() {
+            return DidHandled::Handled;
+        }
+
+        let invocation = self.formated_invocation(app_name);
+
+        if !std::io::stdin().is_terminal() {
+            let root_name = root_app_name(app_name);
+            return DidHandled::Failed(format!(
+                "{}: {} 需要确认才能执行, 但是 stdin 不是终端.\n使用 {} 或者设置环境变量 {} 跳过确认.",
+                "危险操作".styled_error_marker(),
+                invocation,
+                format!("{} --yes {} ...", root_name, self._cmd_name).styled_sub_command(),
+                format!("{}=1", yes_env_var(root_name)).styled_arg(),
+            ));
+        }
+
+        let re = dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+            .with_prompt(format!(
+                "{} {} {}\n确认执行?",
+                "危险操作:".styled_error_marker(),
+                invocation,
+                args.styled_arg()
+            ))
+            .default(false)
+            .wait_for_newline(true)
+            .interact();
+
+        match re {
+            Ok(true) => DidHandled::Handled,
+            Ok(false) => DidHandled::Failed(format!("{} 已取消.", invocation)),
+            Err(err) => DidHandled::Failed(err.to_string()),
+        }
+    }
+
+    /// 逐个参数执行 `each`, 打印每个参数的执行结果.
+    /// 返回值中的 bool 表示是否有参数执行失败.
+    fn run_each(
+        &self,
+        each: &ItemAction,
+        v: SubcommandArgsValue,
+        need_to: NeedTo,
+        dry_run_title: &str,
+        confirm: &dyn Fn(&str) -> DidHandled,
+    ) -> (DidHandled, bool) {
+        fn run<T>(
+            items: ParseResult<T>,
+            need_to: NeedTo,
+            dry_run_title: &str,
+            confirm: &dyn Fn(&str) -> DidHandled,
+            func: &dyn Fn(T) -> Vec<ItemResult>,
+        ) -> Result<Option<Vec<ItemResult>>, String>
+        where
+            T: std::fmt::Debug,
+        {
+            let items = items?;
+            if need_to.is_dry_run() {
+                println!("{} {}", dry_run_title, format!("{:?}", items).styled_arg());
+            }
+            if !need_to.is_run() {
+                return Ok(None);
+            }
+            if let DidHandled::Failed(err) = confirm(&format!("{:?}", items)) {
+                return Err(err);
+            }
+
+            Ok(Some(func(items)))
+        }
+
+        let workers = self._workers;
+        let re = match each {
+            ItemAction::String(f) => {
+                run(v.get_vec_string(), need_to, dry_run_title, confirm, &|x| {
+                    each::run_items(x.to_vec(), *f, workers)
+                })
+            }
+            ItemAction::Number(f) => {
+                run(v.get_vec_number(), need_to, dry_run_title, confirm, &|x| {
+                    each::run_items(x, *f, workers)
+                })
+            }
+            ItemAction::Path(f) => run(v.get_vec_path(), need_to, dry_run_title, confirm, &|x| {
+                each::run_items(x, *f, workers)
+            }),
+            ItemAction::Bool(f) => run(v.get_vec_bool(), need_to, dry_run_title, confirm, &|x| {
+                each::run_items(x, *f, workers)
+            }),
+        };
+
+        match re {
+            Ok(Some(results)) => {
+                let re = EachResult {
+                    cmd_name: self._cmd_name.clone(),
+                    results,
+                };
+                println!("{}", re.formated_massage());
+
+                match re.failed_count() {
+                    0 => (DidHandled::Handled, false),
+                    n => (
+                        DidHandled::Failed(format!(
+                            "{}: {} 个参数执行失败.",
+                            self._cmd_name.styled_sub_command(),
+                            n.styled_error_marker()
+                        )),
+                        true,
+                    ),
+                }
+            }
+            Ok(None) => (DidHandled::Handled, false),
+            Err(err) => (DidHandled::Failed(err), false),
+        }
+    }
+
+    /// `Arg::Dialog` 没有参数时, 使用环境变量 `<APP>_<CMD>_ANSWERS` 中的答案文件.
+    /// 在调用 hook 之前替换参数, 所以 hook 和 action 接收到的参数一样.
+    pub(crate) fn args_with_answers_env(
+        &self,
+        app_name: &str,
+        cmd_args: SharedVecString,
+    ) -> SharedVecString {
+        match &self._arg_type_with_action {
+            Arg::Dialog(_) if cmd_args.is_empty() => {
+                match std::env::var(answers_env_var(app_name, &self._cmd_name)) {
+                    Ok(path) if !path.is_empty() => vec![path].into(),
+                    _ => cmd_args,
+                }
+            }
+            _ => cmd_args,
+        }
+    }
+
+    /// 执行 `Arg::Dialog` 的 action.
+    /// * `toml_string`: 之前保存的答案, 没有时启动问答式交互, stdin 不是终端时从 stdin 每行读取一个答案.
+    /// * `confirm`: 危险的子命令在执行 action 之前请求用户确认, 使用答案文件时显示其中的答案.
+    ///
+    /// 问答中出现错误 (例如缺少答案) 时返回 `DidHandled::Failed`, 即使 action 中使用了 `unwrap()`.
+    fn run_dialog(
+        &self,
+        app_name: &str,
+        f: &dyn Fn(&mut arg_type::Dialog),
+        toml_string: Option<String>,
+        need_to: NeedTo,
+        confirm: &dyn Fn(&str) -> DidHandled,
+    ) -> DidHandled {
+        if !need_to.is_run() {
+            return DidHandled::Handled;
+        }
+
+        let mut repl = match toml_string {
+            Some(toml_string) => match arg_type::Dialog::new_from_toml(&toml_string) {
+                Ok(repl) => repl,
+                // 答案文件不是正确的 toml.
+                Err(err) => return DidHandled::Failed(err),
+            },
+            None if question_and_anser::is_interactive() => arg_type::Dialog::new(),
+            None => {
+                let answers_env = answers_env_var(app_name, &self._cmd_name);
+                arg_type::Dialog::new_from_lines(
+                    Box::new(std::io::stdin().lock()),
+                    format!(
+                        "也可以使用答案文件: {} {} {} 或者 {}",
+                        app_name,
+                        self._cmd_name,
+                        "<答案文件>".styled_arg(),
+                        format!("{}=<答案文件>", answers_env).styled_arg(),
+                    ),
+                )
+            }
+        };
+
+        let args = if repl.is_from_toml {
+            format!("\n{}", repl.to_toml().unwrap_or_default())
+        } else {
+            "(问答式交互)".to_string()
+        };
+        if let DidHandled::Failed(err) = confirm(&args) {
+            return DidHandled::Failed(err);
+        }
+
+        if let Err(err) = question_and_anser::run_action(f, &mut repl) {
+            return DidHandled::Failed(err);
+        }
+
+        if !repl.is_from_toml || repl.has_new_answers {
+            // 补充了 toml 中没有的答案时, 打印完整的答案.
+            repl.finesh_and_print(app_name, &self._cmd_name);
+        }
+
+        DidHandled::Handled
+    }
+
     fn formated_usage(&self, app_name: &str) -> String {
         let command_name = self._cmd_name.bright_cyan();
         let short_name = self._short_name.bright_cyan();
@@ -161,6 +590,16 @@ impl<'a> SubCommand {
             Arg::Dialog(_) => "".to_string(),
         };
 
+        if let Some(invoked_as) = &self._invoked_as {
+            return format!(
+                r#"
+{usg}:
+    {invoked_as} {arg_in_usage}"#,
+                usg = "Usage".bright_green(),
+                invoked_as = invoked_as.cyan(),
+            );
+        }
+
         // let arg_in_usage = arg_in_usage;
         let app_name = app_name.cyan();
         let a = format!(
@@ -188,9 +627,14 @@ impl<'a> SubCommand {
     /// 自动生成的 子命令帮助文档.
     /// `app cmd -h` 时显示的帮助文档.
     fn formated_command_help(&self, app_name: &str) -> String {
+        let deprecation = match &self._deprecated {
+            Some(d) => format!("\n{}\n", d.formated(app_name, &self._cmd_name)),
+            None => String::new(),
+        };
+
         if let Some(s) = &self._help_message {
             // 自定义了帮助文档的情况;
-            s.to_string()
+            format!("{}{}", deprecation, s)
         } else {
             // 自动生成这个 Command 的帮助文档
 
@@ -222,7 +666,7 @@ impl<'a> SubCommand {
             };
 
             let message = format!(
-                r#"
+                r#"{deprecation}
 {about}
 {Usage}
 {arg_message}
@@ -238,28 +682,77 @@ impl<'a> SubCommand {
         }
     }
 
-    // pub(crate) fn formated_row_in_list_all_command(&self) -> Vec<Row> {
-    //     let x = self;
-    //     let short_name = if x._short_name.is_empty() {
-    //         "".to_string()
-    //     } else {
-    //         format!("{}{}", &x._short_name, ", ")
-    //     };
+    /// `app --list-all-commands` 时显示的行.
+    /// 第一行是子命令本身, 如果有 short_name, 第二行是 short_name.
+    /// * `prefix`: 挂载的 App 中的子命令需要加上挂载的名称, 例如 `"tool "`.
+    pub(crate) fn formated_row_in_list_all_command(&self, prefix: &str) -> Vec<Row> {
+        let x = self;
+        let command_name = &format!("{}{}", prefix, x._cmd_name);
+
+        let about = if x._deprecated.is_some() {
+            format!("{} {}", "(deprecated)".bright_yellow(), x._about)
+        } else {
+            x._about.clone()
+        };
 
-    //     let command_name = &x._cmd_name;
+        let mut result: Vec<Row> = vec![];
+
+        result.push(row![
+            command_name.styled_sub_command(),
+            x._arg_type_with_action.arg_message().trim(),
+            about,
+            format!("{} examples", x._exaples.val.len())
+        ]);
+        if !x._short_name.is_empty() {
+            result.push(row![
+                format!("{}{}", prefix, x._short_name).styled_sub_command(),
+                format!("alias: {}", command_name.styled_sub_command())
+            ]);
+        }
 
-    //     let mut result: Vec<Row> = vec![];
+        result
+    }
 
-    //     result.push(row![command_name.styled_sub_command(), x._about]);
-    //     if !x._short_name.is_empty() {
-    //         result.push(row![
-    //             short_name.styled_sub_command(),
-    //             format!("alias: {}", command_name.styled_sub_command())
-    //         ]);
-    //     }
+    /// 通过问答式交互让用户输入此子命令需要的参数.
+    /// 返回的参数和用户在命令行中输入的参数一样, 可以直接交给 `sub_command_try_run` 处理.
+    pub(crate) fn ask_for_args(&self) -> Result<Vec<String>, String> {
+        let theme = dialoguer::theme::ColorfulTheme::default();
+        let prompt = format!(
+            "{} {}",
+            self._cmd_name,
+            self._arg_type_with_action.arg_type_display()
+        );
 
-    //     result
-    // }
+        let re = match &self._arg_type_with_action {
+            Arg::Empty(_) | Arg::Dialog(_) => vec![],
+            Arg::String(_) | Arg::Path(_) => vec![DialogerWraper::get_string(&prompt, &theme)?],
+            Arg::Number(_) => vec![DialogerWraper::get_number(&prompt, &theme)?.to_string()],
+            Arg::Bool(_) => vec![DialogerWraper::get_bool(&prompt, &theme)?.to_string()],
+            Arg::StringMutiple(_)
+            | Arg::NumberMutiple(_)
+            | Arg::PathMutiple(_)
+            | Arg::BoolMutiple(_) => DialogerWraper::get_string_multiple(&prompt, &theme)?,
+        };
+
+        Ok(re)
+    }
+
+    /// `app --list-all-commands toml` 时使用的信息.
+    pub(crate) fn command_info(&self, prefix: &str) -> CommandInfo {
+        CommandInfo {
+            name: format!("{}{}", prefix, self._cmd_name),
+            short_name: if self._short_name.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", prefix, self._short_name)
+            },
+            arg_type: self._arg_type_with_action.arg_type_display(),
+            about: self._about.clone(),
+            group: self._group.clone(),
+            examples: self._exaples.val.len(),
+            deprecated: self._deprecated.is_some(),
+        }
+    }
 
     /// 已经格式化好了, 直接放进 Table 打印就行.
     fn formated_command_example(&self, app_name: &str) -> Vec<Row> {
@@ -309,6 +802,12 @@ impl<'a> SubCommand {
         cmd_args: SharedVecString,
         need_to: NeedTo,
     ) -> DidHandled {
+        if let Some(app) = &self._mounted_app {
+            // 挂载的 App 自己处理 -h -e -v 等 flag.
+            let prefix = format!("{} {}", app_name, self._cmd_name);
+            return app.mounted_try_run(&prefix, cmd_args, need_to);
+        }
+
         {
             // 处理当前 子命令 的 flag.
             if let Some(first_arg) = cmd_args.first().cloned() {
@@ -330,58 +829,112 @@ impl<'a> SubCommand {
             }
         }
 
+        if let Some(d) = &self._deprecated {
+            if need_to.is_run() {
+                eprintln!("{}", d.formated(app_name, &self._cmd_name));
+            }
+        }
+
+        if need_to.is_run() {
+            if let Err(err) = hook::run_hooks(&self._before_hooks, &self._cmd_name, &cmd_args) {
+                return DidHandled::Failed(err);
+            }
+        }
+
         {
             let arg_message = self._arg_type_with_action.arg_message();
 
-            let v = SubcommandArgsValue::new(cmd_args);
+            let v = SubcommandArgsValue::new(cmd_args.clone());
 
-            let re = match &self._arg_type_with_action {
-                Arg::Empty(f) => run(v.get_empty(), need_to, f),
-                Arg::String(f) => run(v.get_string(), need_to, f),
-                Arg::StringMutiple(f) => run(v.get_vec_string(), need_to, f),
-                Arg::Number(f) => run(v.get_number(), need_to, f),
-                Arg::NumberMutiple(f) => run(v.get_vec_number(), need_to, f),
-                Arg::Path(f) => run(v.get_path(), need_to, f),
-                Arg::PathMutiple(f) => run(v.get_vec_path(), need_to, f),
-                Arg::Bool(f) => run(v.get_bool(), need_to, f),
-                Arg::BoolMutiple(f) => run(v.get_vec_bool(), need_to, f),
-                Arg::Dialog(f) => run(v.get_repl(), need_to, &|s| {
-                    match s {
-                        Some(json_string) => {
-                            /* 收到了参数 "stdin" */
-                            let re = &mut arg_type::Dialog::new_from_toml(json_string.as_str());
-                            match re {
+            let dry_run_title = format!(
+                "{} {}",
+                "dry run:".bright_yellow(),
+                self.formated_invocation(app_name)
+            );
+
+            let confirm = |args: &str| self.confirm_dangerous(app_name, args, need_to);
+
+            let mut has_failed_item = false;
+
+            let re = if let Some(each) = &self._each {
+                let (re, failed) = self.run_each(each, v, need_to, &dry_run_title, &confirm);
+                has_failed_item = failed;
+                re
+            } else {
+                match &self._arg_type_with_action {
+                    Arg::Dialog(_f) if need_to.is_dry_run() => match v.get_repl() {
+                        Ok(Some(toml_string)) => {
+                            match arg_type::Dialog::new_from_toml(&toml_string) {
                                 Ok(repl) => {
-                                    f(repl); // repl.finesh_and_print(app_name, self._name);
-                                }
-                                Err(err) => {
-                                    eprintln!("{}", err);
-                                    // json_string 解码为 Vec<String> 时发生错误.
-                                    panic!(
-                                        "\n参数不正确.\nsee {} {} {} for more infomation.\n",
-                                        app_name.magenta(),
-                                        self._cmd_name.styled_sub_command(),
-                                        "-h".styled_arg()
+                                    println!(
+                                        "{dry_run_title}\n{}",
+                                        repl.to_toml().unwrap_or_default().green()
                                     );
+                                    DidHandled::Handled
                                 }
+                                Err(err) => DidHandled::Failed(err),
                             }
                         }
-                        None => {
-                            /* 该子命令没有收到参数, 启动问答式交互 */
-
-                            let mut repl = arg_type::Dialog::new();
-                            f(&mut repl);
-                            repl.finesh_and_print(app_name, &self._cmd_name);
+                        Ok(None) => {
+                            // 不执行 action 就无法知道会问哪些问题, 只能提示使用答案文件.
+                            let root_name = root_app_name(app_name);
+                            println!(
+                                "{dry_run_title} (问答式交互, 使用答案文件才能显示答案: {} {} {} 或者 {})",
+                                app_name.replacen(root_name, &format!("{root_name} --dry-run"), 1),
+                                self._cmd_name,
+                                "<答案文件>".styled_arg(),
+                                format!(
+                                    "{}=<答案文件>",
+                                    answers_env_var(app_name, &self._cmd_name)
+                                )
+                                .styled_arg(),
+                            );
+                            DidHandled::Handled
                         }
+                        Err(err) => DidHandled::Failed(err),
+                    },
+                    Arg::Empty(f) => run(v.get_empty(), need_to, f, &dry_run_title, &confirm),
+                    Arg::String(f) => run(v.get_string(), need_to, f, &dry_run_title, &confirm),
+                    Arg::StringMutiple(f) => {
+                        run(v.get_vec_string(), need_to, f, &dry_run_title, &confirm)
+                    }
+                    Arg::Number(f) => run(v.get_number(), need_to, f, &dry_run_title, &confirm),
+                    Arg::NumberMutiple(f) => {
+                        run(v.get_vec_number(), need_to, f, &dry_run_title, &confirm)
                     }
-                }),
+                    Arg::Path(f) => run(v.get_path(), need_to, f, &dry_run_title, &confirm),
+                    Arg::PathMutiple(f) => {
+                        run(v.get_vec_path(), need_to, f, &dry_run_title, &confirm)
+                    }
+                    Arg::Bool(f) => run(v.get_bool(), need_to, f, &dry_run_title, &confirm),
+                    Arg::BoolMutiple(f) => {
+                        run(v.get_vec_bool(), need_to, f, &dry_run_title, &confirm)
+                    }
+                    Arg::Dialog(f) => match v.get_repl() {
+                        Ok(toml_string) => {
+                            self.run_dialog(app_name, *f, toml_string, need_to, &confirm)
+                        }
+                        Err(err) => DidHandled::Failed(err),
+                    },
+                }
             };
 
+            if need_to.is_run() {
+                let after = hook::run_hooks(&self._after_hooks, &self._cmd_name, &cmd_args);
+                if let (DidHandled::Handled, Err(err)) = (&re, after) {
+                    return DidHandled::Failed(err);
+                }
+            }
+
+            if has_failed_item {
+                // 逐个参数执行时有参数执行失败, 每个参数的结果已经打印过了, 不需要再打印帮助信息.
+                return re;
+            }
+
             if let DidHandled::Failed(err) = re {
                 let tips = format!(
-                    "输入  {} {} {}  查看更详细信息.",
-                    app_name.styled_sub_command(),
-                    self._cmd_name.styled_sub_command(),
+                    "输入  {} {}  查看更详细信息.",
+                    self.formated_invocation(app_name),
                     "-h".styled_sub_command(),
                 );
                 return DidHandled::Failed(format!(
@@ -398,16 +951,23 @@ impl<'a> SubCommand {
 
             return re;
 
-            fn run<T>(
+            fn run<T: std::fmt::Debug>(
                 result: ParseResult<T>,
                 // need_run_action: bool,
                 need_to: NeedTo,
                 func: &dyn Fn(T),
+                dry_run_title: &str,
+                confirm: &dyn Fn(&str) -> DidHandled,
             ) -> DidHandled {
                 match result {
                     Ok(s) => {
                         if need_to.is_run() {
+                            if let DidHandled::Failed(err) = confirm(&format!("{:?}", s)) {
+                                return DidHandled::Failed(err);
+                            }
                             func(s);
+                        } else if need_to.is_dry_run() {
+                            println!("{} {}", dry_run_title, format!("{:?}", s).styled_arg());
                         }
                         DidHandled::Handled
                     }
@@ -423,6 +983,11 @@ impl<'a> SubCommand {
         app_name: &str,
         cmd_args: SharedVecString,
     ) -> DidHandled {
+        if let Some(app) = &self._mounted_app {
+            let prefix = format!("{} {}", app_name, self._cmd_name);
+            return app.mounted_try_run(&prefix, cmd_args, NeedTo::ParseOnly);
+        }
+
         {
             // 处理当前 子命令 的 flag.
             if let Some(first_arg) = cmd_args.first().cloned() {
@@ -486,7 +1051,7 @@ impl<'a> SubCommand {
 
     /// 测试命令是否能够被匹配
     pub(crate) fn debug_cmd_example_check(&'a self, app_name: &str) -> ExampleTestResult<'a> {
-        let mut bad_examples = ExampleTestResult::new(self);
+        let mut bad_examples = ExampleTestResult::new(&self._cmd_name);
 
         for exam in &self._exaples.val {
             let mut wait_to_putsh = Sadadsf {
@@ -497,8 +1062,8 @@ impl<'a> SubCommand {
             let cmd_arg = {
                 let mut virtual_env_args = helper::parse_arg_string(&exam.command);
                 if !virtual_env_args.is_empty() {
-                    let name = virtual_env_args.remove(0); // 移除 app name
-                    if app_name != name {
+                    // 移除 app name
+                    if let Err(name) = helper::remove_app_name(&mut virtual_env_args, app_name) {
                         let err_msg = format!(
                             "{}: 需要 {}; 实际收到的: {:?}",
                             "程序名称错误".bright_red(),
@@ -530,7 +1095,7 @@ impl<'a> SubCommand {
             let re = self.sub_command_try_parse(app_name, cmd_arg.into());
             match re {
                 DidHandled::Handled => bad_examples.success_examples.push(exam),
-                DidHandled::Failed(err_msg) => {
+                DidHandled::Failed(err_msg) | DidHandled::FailedWithCode(_, err_msg) => {
                     wait_to_putsh.err_msg.push(err_msg);
                 }
             }
@@ -545,15 +1110,16 @@ impl<'a> SubCommand {
 }
 
 pub(crate) struct ExampleTestResult<'a> {
-    cmd: &'a SubCommand,
-    failures_examples: Vec<Sadadsf<'a>>,
-    success_examples: Vec<&'a SingleExample>,
+    /// 子命令的名称, 或者是 app 的名称.
+    name: &'a str,
+    pub(crate) failures_examples: Vec<Sadadsf<'a>>,
+    pub(crate) success_examples: Vec<&'a SingleExample>,
 }
 
 impl<'a> ExampleTestResult<'a> {
-    pub fn new(cmd: &'a SubCommand) -> Self {
+    pub fn new(name: &'a str) -> Self {
         Self {
-            cmd,
+            name,
             failures_examples: vec![],
             success_examples: vec![],
         }
@@ -576,15 +1142,7 @@ impl<'a> ExampleTestResult<'a> {
             .fold("".to_string(), |x, y| x + &y);
 
         let mut table = table!();
-        {
-            let mut f = TableFormat::new();
-            f.padding(2, 0);
-            f.separator(LinePosition::Bottom, LineSeparator::new('─', 'j', '└', 'r'));
-            // f.separator(LinePosition::Title, LineSeparator::new('─', 'j', '├', 'r'));
-            f.separator(LinePosition::Title, LineSeparator::new('━', 'j', '┝', 'r'));
-            f.left_border('│');
-            table.set_format(f);
-        }
+        table.set_format(helper::result_table_formater());
         table.add_row(row![msgs]);
 
         /* return */
@@ -592,12 +1150,12 @@ impl<'a> ExampleTestResult<'a> {
             format!(
                 r#"example test for {cmd_name} ... {ok}
 "#,
-                cmd_name = self.cmd._cmd_name.styled_sub_command(),
+                cmd_name = self.name.styled_sub_command(),
             )
         } else {
             let title = format!(
                 r#"example test for {cmd_name} ... {ok}"#,
-                cmd_name = self.cmd._cmd_name.styled_sub_command(),
+                cmd_name = self.name.styled_sub_command(),
             );
             table.set_titles(row![title]);
             format!("\n{}", table)
@@ -607,9 +1165,9 @@ impl<'a> ExampleTestResult<'a> {
 
 /// 存储一个 SingleExample 中出现的多个错误.
 pub(crate) struct Sadadsf<'a> {
-    base: &'a SingleExample,
+    pub(crate) base: &'a SingleExample,
 
-    err_msg: Vec<String>,
+    pub(crate) err_msg: Vec<String>,
 }
 
 impl Sadadsf<'_> {
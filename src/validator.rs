@@ -0,0 +1,160 @@
+//! `Arg::Dialog` 中问题的答案的校验.
+
+use crate::helper::*;
+
+/// 自定义的校验函数, 参数是文字形式的答案, 多个值时对每个值分别调用.
+pub type ValidatorFn = dyn Fn(&str) -> Result<(), String>;
+
+/// 答案的校验, 请使用 `DialogGenerator::validate()` 设置.
+/// 交互式问答时校验失败会重新提问, 从 toml 读取答案时校验失败会返回错误.
+/// ```
+/// use chenbao_cmd::*;
+///     cmd!("serve").action(Arg::Dialog(&|r| {
+///         let _port = r
+///             .validate(Validator::Range(1, 65535))
+///             .number("端口")
+///             .unwrap();
+///     }));
+/// ```
+#[derive(Clone)]
+pub enum Validator {
+    /// 数字在范围内, 包括两端. 多个值时每个值都需要在范围内.
+    Range(i64, i64),
+
+    /// 匹配正则表达式. 多个值时每个值都需要匹配.
+    Regex(&'static str),
+
+    /// 不能是空字符串. 多个值时至少需要一个值, 并且每个值都不能是空字符串.
+    NonEmpty,
+
+    /// 多个值时值的数量在范围内, 包括两端.
+    Count(usize, usize),
+
+    /// 路径必须存在. 多个值时每个路径都必须存在.
+    PathExists,
+
+    /// 自定义的校验.
+    Custom(&'static ValidatorFn),
+}
+
+impl std::fmt::Debug for Validator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Range(min, max) => f.debug_tuple("Range").field(min).field(max).finish(),
+            Self::Regex(re) => f.debug_tuple("Regex").field(re).finish(),
+            Self::NonEmpty => f.debug_tuple("NonEmpty").finish(),
+            Self::Count(min, max) => f.debug_tuple("Count").field(min).field(max).finish(),
+            Self::PathExists => f.debug_tuple("PathExists").finish(),
+            Self::Custom(_arg0) => f.debug_tuple("Custom(_)").finish(),
+        }
+    }
+}
+
+impl Validator {
+    /// 检查 validator 本身是否正确, 例如 `Regex` 的正则表达式, 并且只编译一次正则表达式.
+    pub(crate) fn compile(self) -> Result<CompiledValidator, String> {
+        let regex = match &self {
+            Validator::Regex(pattern) => Some(
+                regex::Regex::new(pattern)
+                    .map_err(|err| format!("不正确的正则表达式 {}: {}", pattern, err))?,
+            ),
+            _ => None,
+        };
+
+        Ok(CompiledValidator {
+            validator: self,
+            regex,
+        })
+    }
+}
+
+/// 检查过的 validator, 重新提问时不需要再次编译正则表达式.
+#[derive(Clone, Debug)]
+pub(crate) struct CompiledValidator {
+    validator: Validator,
+
+    /// `Validator::Regex` 编译后的正则表达式.
+    regex: Option<regex::Regex>,
+}
+
+impl CompiledValidator {
+    /// 校验文字形式的答案, 单个值时 `values` 只有一个元素.
+    pub(crate) fn validate(&self, values: &[String]) -> Result<(), String> {
+        match &self.validator {
+            Validator::Range(min, max) => {
+                values
+                    .iter()
+                    .try_for_each(|x| match x.trim().parse::<i64>() {
+                        Ok(n) if (*min..=*max).contains(&n) => Ok(()),
+                        _ => Err(format!(
+                            "{} 需要是 {} 到 {} 之间的数字",
+                            x.styled_arg(),
+                            min,
+                            max
+                        )),
+                    })
+            }
+            Validator::Regex(pattern) => {
+                let Some(re) = &self.regex else {
+                    return Ok(());
+                };
+                values.iter().try_for_each(|x| {
+                    if re.is_match(x) {
+                        Ok(())
+                    } else {
+                        Err(format!("{} 需要匹配 {}", x.styled_arg(), pattern))
+                    }
+                })
+            }
+            Validator::NonEmpty => {
+                if values.is_empty() || values.iter().any(|x| x.trim().is_empty()) {
+                    Err("不能为空".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            Validator::Count(min, max) => {
+                if (*min..=*max).contains(&values.len()) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "需要 {} 到 {} 个值, 实际是 {} 个",
+                        min,
+                        max,
+                        values.len()
+                    ))
+                }
+            }
+            Validator::PathExists => values.iter().try_for_each(|x| {
+                if std::path::Path::new(x).exists() {
+                    Ok(())
+                } else {
+                    Err(format!("路径不存在: {}", x.styled_arg()))
+                }
+            }),
+            Validator::Custom(f) => values.iter().try_for_each(|x| f(x)),
+        }
+    }
+}
+
+#[test]
+fn test_validator() {
+    let v = |x: &[&str]| x.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let check = |validator: Validator, values: &[&str]| validator.compile()?.validate(&v(values));
+
+    assert!(check(Validator::Range(1, 10), &["1", "10"]).is_ok());
+    assert!(check(Validator::Range(1, 10), &["11"]).is_err());
+    assert!(check(Validator::Regex("^v[0-9]+$"), &["v1"]).is_ok());
+    assert!(check(Validator::Regex("^v[0-9]+$"), &["1"]).is_err());
+    assert!(Validator::Regex("(").compile().is_err());
+    assert!(check(Validator::NonEmpty, &[" "]).is_err());
+    assert!(check(Validator::NonEmpty, &[]).is_err());
+    assert!(check(Validator::Count(1, 2), &["a", "b", "c"]).is_err());
+    assert!(check(Validator::PathExists, &["./Cargo.toml"]).is_ok());
+    assert!(check(Validator::PathExists, &["./not_exists"]).is_err());
+    assert!(check(
+        Validator::Custom(&|x| if x == "a" { Ok(()) } else { Err(x.to_string()) }),
+        &["a", "b"]
+    )
+    .is_err());
+}
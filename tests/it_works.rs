@@ -85,7 +85,8 @@ fn it_works() {
                     )),
             );
 
-    app.clone().debug_check().run();
+    // 和不带参数运行程序一样; `run()` 执行失败时会退出进程, 不能在测试中使用.
+    app.clone().debug_check().deubug_run(["cmd"]);
 
     // let re = app.debug_duplicate_names_check();
     // match re {
@@ -285,3 +286,247 @@ fn maasdfasfin() {
     let b = Rc::clone(&a);
     let _c = b.clone();
 }
+
+#[test]
+fn hidden_and_deprecated() {
+    let _ = App::new()
+        .app_name("app")
+        .add_command(cmd!("build").about("编译项目").action(Arg::Empty(&|_x| {
+            println!("building");
+        })))
+        .add_command(
+            cmd!("compile")
+                .about("编译项目")
+                .hidden(true)
+                .deprecated("build", "compile 将在下一个版本中移除.")
+                .action(Arg::Empty(&|_x| {
+                    println!("compiling");
+                })),
+        )
+        .deubug_run(["app", "-h"])
+        .deubug_run(["app", "compile"])
+        .deubug_run(["app", "compile", "-h"]);
+}
+
+#[test]
+fn derive_from_dialog() {
+    #[derive(FromDialog, Debug)]
+    struct Deploy {
+        #[dialog(prompt = "部署到哪个环境?", choices("dev", "prod"))]
+        env: String,
+
+        #[dialog(prompt = "实例数量", key = "instance_count", validator = check_count)]
+        count: i64,
+
+        verbose: bool,
+
+        files: Vec<std::path::PathBuf>,
+
+        #[dialog(default = 8080, help = "1024 以下的端口需要 root 权限")]
+        port: i64,
+
+        server: Server,
+
+        #[dialog(prompt = "添加一个?")]
+        backups: Vec<Server>,
+    }
+
+    #[derive(FromDialog, Debug)]
+    struct Server {
+        host: String,
+    }
+
+    fn check_count(x: &str) -> Result<(), String> {
+        if x.parse::<i64>().is_ok_and(|x| x > 0) {
+            Ok(())
+        } else {
+            Err("需要大于 0".to_string())
+        }
+    }
+
+    let toml = r#"
+env = "prod"
+instance_count = 3
+files = ["a.txt", "b.txt"]
+port = 80
+
+# 没有 key 时使用 "{序号}_{问题}"
+"03_verbose" = true
+
+[server]
+host = "example.com"
+
+[[backups]]
+host = "a.example.com"
+
+[[backups]]
+host = "b.example.com"
+"#;
+    let mut r = arg_type::Dialog::new_from_toml(toml).unwrap();
+    let deploy = Deploy::from_dialog(&mut r).unwrap();
+    assert_eq!(deploy.env, "prod");
+    assert_eq!(deploy.count, 3);
+    assert!(deploy.verbose);
+    assert_eq!(deploy.files.len(), 2);
+    assert_eq!(deploy.port, 80);
+    assert_eq!(deploy.server.host, "example.com");
+    assert_eq!(deploy.backups.len(), 2);
+
+    let mut r = arg_type::Dialog::new_from_toml(&toml.replace("= 3", "= 0")).unwrap();
+    assert!(Deploy::from_dialog(&mut r).is_err());
+}
+
+#[test]
+fn partial_replay() {
+    let mut r = arg_type::Dialog::new_from_toml("name = \"app\"").unwrap();
+    assert_eq!(r.key("name").string("名称").unwrap(), "app");
+    assert!(r.key("name").number("类型不正确").is_err());
+
+    // toml 中的答案校验失败时返回错误.
+    let err = r
+        .key("name")
+        .validate(Validator::Regex("^[0-9]+$"))
+        .string("名称")
+        .unwrap_err();
+    assert!(err.contains("name"));
+
+    // `Default` 依然可以使用.
+    let r = arg_type::Dialog::default();
+    assert!(!r.is_from_toml);
+}
+
+#[test]
+fn replay_from_file() {
+    let path = std::env::temp_dir().join("chenbao_cmd_replay_from_file.answers.toml");
+    std::fs::write(&path, "name = \"app\"").unwrap();
+
+    let app = App::new()
+        .app_name("app")
+        .add_command(cmd!("new").action(Arg::Dialog(&|r| {
+            assert_eq!(r.key("name").string("名称").unwrap(), "app");
+        })));
+
+    let script = std::env::temp_dir().join("chenbao_cmd_replay_from_file.txt");
+    std::fs::write(&script, format!("app new {}", path.display())).unwrap();
+
+    let re = app.clone().run_script(&script.display().to_string(), false);
+    assert_eq!(re, DidHandled::Handled);
+
+    // 答案文件不是正确的 toml 时返回错误, 不会 panic.
+    std::fs::write(&path, "name = ").unwrap();
+    let re = app.run_script(&script.display().to_string(), false);
+    assert!(re != DidHandled::Handled);
+}
+
+#[test]
+fn replay_from_answers_env() {
+    let path = std::env::temp_dir().join("chenbao_cmd_replay_from_answers_env.answers.toml");
+
+    if std::env::var_os("ENVAPP_NEW_ANSWERS").is_none() {
+        // 在子进程中设置环境变量, 避免和其他同时执行的测试一起读写环境变量.
+        std::fs::write(&path, "name = \"app\"").unwrap();
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["replay_from_answers_env", "--exact", "--quiet"])
+            .env("ENVAPP_NEW_ANSWERS", &path)
+            .stdin(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        return;
+    }
+
+    let app = App::new()
+        .app_name("envapp")
+        .before(&|name, args| {
+            // hook 接收到的参数是环境变量中的答案文件.
+            if name == "new" && args.is_empty() {
+                Err("hook 没有接收到答案文件".to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .add_command(cmd!("new").action(Arg::Dialog(&|r| {
+            assert_eq!(r.key("name").string("名称").unwrap(), "app");
+        })))
+        .add_command(cmd!("other").action(Arg::Dialog(&|r| {
+            // 其他子命令不使用这个答案文件.
+            assert!(!r.is_from_toml);
+        })));
+
+    // 没有参数时使用 ENVAPP_NEW_ANSWERS 中的答案文件.
+    let script = std::env::temp_dir().join("chenbao_cmd_replay_from_answers_env.txt");
+    std::fs::write(&script, "envapp new").unwrap();
+
+    let re = app.clone().run_script(&script.display().to_string(), false);
+    assert_eq!(re, DidHandled::Handled);
+
+    std::fs::write(&script, "envapp other").unwrap();
+    let re = app.run_script(&script.display().to_string(), false);
+    assert_eq!(re, DidHandled::Handled);
+}
+
+#[test]
+fn dialog_sections() {
+    let toml = r#"
+use_tls = true
+"03_端口" = 443
+
+[tls]
+"01_证书路径" = "./cert.pem"
+"#;
+
+    let ask = |r: &mut arg_type::Dialog| -> Result<(Option<std::path::PathBuf>, i64), String> {
+        let use_tls = r.key("use_tls").yes_or_no("使用 TLS?")?;
+        let cert = r.when(use_tls, "tls", |r| r.path("证书路径"))?;
+        let port = r.number("端口")?;
+        Ok((cert, port))
+    };
+
+    let mut r = arg_type::Dialog::new_from_toml(toml).unwrap();
+    let (cert, port) = ask(&mut r).unwrap();
+    assert_eq!(cert, Some(std::path::PathBuf::from("./cert.pem")));
+    assert_eq!(port, 443);
+    let saved = r.to_toml().unwrap();
+    let mut r = arg_type::Dialog::new_from_toml(&saved).unwrap();
+    assert_eq!(ask(&mut r).unwrap().0, cert);
+
+    // 不使用 TLS 时, 后面的问题的 key 不变.
+    let toml = toml.replace("use_tls = true", "use_tls = false");
+    let mut r = arg_type::Dialog::new_from_toml(&toml).unwrap();
+    let (cert, port) = ask(&mut r).unwrap();
+    assert_eq!(cert, None);
+    assert_eq!(port, 443);
+
+    // 保存的 toml 可以再次使用.
+    let saved = r.to_toml().unwrap();
+    let mut r = arg_type::Dialog::new_from_toml(&saved).unwrap();
+    assert_eq!(ask(&mut r).unwrap(), (None, 443));
+}
+
+#[test]
+fn dialog_repeat() {
+    let toml = r#"
+[[hosts]]
+name = "a"
+port = 22
+
+[[hosts]]
+name = "b"
+port = 2222
+"#;
+
+    let ask = |r: &mut arg_type::Dialog| {
+        r.repeat("hosts", "添加一个 host?", |r| {
+            Ok((r.key("name").string("名称")?, r.key("port").number("端口")?))
+        })
+    };
+
+    let mut r = arg_type::Dialog::new_from_toml(toml).unwrap();
+    let hosts = ask(&mut r).unwrap();
+    assert_eq!(hosts, vec![("a".to_string(), 22), ("b".to_string(), 2222)]);
+
+    let saved = r.to_toml().unwrap();
+    assert!(saved.contains("[[hosts]]"));
+    let mut r = arg_type::Dialog::new_from_toml(&saved).unwrap();
+    assert_eq!(ask(&mut r).unwrap(), hosts);
+}